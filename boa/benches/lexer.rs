@@ -0,0 +1,38 @@
+//! Benchmarks of the lexing process in Boa.
+
+use boa::syntax::lexer::Lexer;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+#[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
+#[cfg_attr(
+    all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"),
+    global_allocator
+)]
+static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
+
+/// Lexes all the tokens of the given source.
+fn lex_all(src: &[u8]) {
+    let mut lexer = Lexer::new(src);
+    while let Some(token) = lexer.next().expect("could not lex the source") {
+        black_box(token);
+    }
+}
+
+fn string_literal(c: &mut Criterion) {
+    // About 4 MB of plain characters, mixed with `\n` and `\x` escape sequences.
+    let src = format!(
+        r#""{}";"#,
+        r"Lorem ipsum dolor sit amet\n\x41\x7e consectetur".repeat(80_000)
+    );
+
+    let mut group = c.benchmark_group("Lexer");
+    group.throughput(Throughput::Bytes(src.len() as u64));
+    group.sample_size(10);
+    group.bench_function("Long string literal (Lexer)", |b| {
+        b.iter(|| lex_all(black_box(src.as_bytes())))
+    });
+    group.finish();
+}
+
+criterion_group!(lexer, string_literal);
+criterion_main!(lexer);
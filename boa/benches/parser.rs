@@ -86,6 +86,14 @@ fn mini_js(c: &mut Criterion) {
     });
 }
 
+fn hex_escapes(c: &mut Criterion) {
+    let src = format!(r#""{}";"#, r"\x41\x7e\xFF\x0a".repeat(2_500));
+
+    c.bench_function("Hexadecimal escapes (Parser)", move |b| {
+        b.iter(|| Parser::new(black_box(src.as_bytes())).parse_all())
+    });
+}
+
 criterion_group!(
     parser,
     expression_parser,
@@ -95,5 +103,6 @@ criterion_group!(
     goal_symbol_switch,
     clean_js,
     mini_js,
+    hex_escapes,
 );
 criterion_main!(parser);
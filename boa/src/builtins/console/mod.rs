@@ -20,7 +20,10 @@ use crate::{
     builtins::BuiltIn,
     object::ObjectInitializer,
     property::Attribute,
-    value::{display::display_obj, RcString, Value},
+    value::{
+        display::{display_obj, DisplayOptions},
+        RcString, Value,
+    },
     BoaProfiler, Context, Result,
 };
 use rustc_hash::FxHashMap;
@@ -526,7 +529,11 @@ impl Console {
     pub(crate) fn dir(_: &Value, args: &[Value], ctx: &mut Context) -> Result<Value> {
         let undefined = Value::undefined();
         logger(
-            LogMessage::Info(display_obj(args.get(0).unwrap_or(&undefined), true)),
+            LogMessage::Info(display_obj(
+                args.get(0).unwrap_or(&undefined),
+                true,
+                DisplayOptions::default(),
+            )),
             ctx.console(),
         );
 
@@ -259,10 +259,10 @@ fn json_parse_object_with_reviver() {
 fn json_parse_sets_prototypes() {
     let mut engine = Context::new();
     let init = r#"
-        const jsonString = "{
-            \"ob\":{\"ject\":1},
-            \"arr\": [0,1]
-        }";
+        const jsonString = `{
+            "ob":{"ject":1},
+            "arr": [0,1]
+        }`;
         const jsonObj = JSON.parse(jsonString);
     "#;
     eprintln!("{}", forward(&mut engine, init));
@@ -1,5 +1,8 @@
 //! Javascript context.
 
+#[cfg(test)]
+mod tests;
+
 use crate::{
     builtins::{
         self,
@@ -8,9 +11,10 @@ use crate::{
         symbol::{Symbol, WellKnownSymbols},
     },
     class::{Class, ClassBuilder},
+    environment::global_environment_record::GlobalEnvironmentRecord,
     exec::Interpreter,
-    object::{GcObject, Object, ObjectData, PROTOTYPE},
-    property::{DataDescriptor, PropertyKey},
+    object::{GcObject, Object, ObjectData, PropertiesSnapshot, PROTOTYPE},
+    property::{Attribute, DataDescriptor, PropertyKey},
     realm::Realm,
     syntax::{
         ast::{
@@ -20,12 +24,25 @@ use crate::{
             },
             Const, Node,
         },
+        lexer::Diagnostic,
+        parser::{self, ParseError},
         Parser,
     },
     value::{RcString, RcSymbol, Value},
     BoaProfiler, Executable, Result,
 };
-use std::result::Result as StdResult;
+use serde_json::Value as JSONValue;
+use std::{
+    io::Read,
+    result::Result as StdResult,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
 
 #[cfg(feature = "console")]
 use crate::builtins::console::Console;
@@ -166,11 +183,27 @@ impl StandardObjects {
     }
 }
 
+/// A copy of the global state of a [`Context`], made with [`Context::snapshot`].
+///
+/// It holds the properties of the global object and the global `let`, `const` and `class`
+/// bindings. The values themselves are not deep-copied: an object held by a global is shared
+/// with the context, so changes made to its properties are not undone by
+/// [`Context::restore`].
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    global_properties: PropertiesSnapshot,
+    global_environment: GlobalEnvironmentRecord,
+}
+
 /// Javascript context. It is the primary way to interact with the runtime.
 ///
 /// `Context`s constructed in a thread share the same runtime, therefore it
 /// is possible to share objects from one context to another context, but they
 /// have to be in the same thread.
+///
+/// All the scripts evaluated in a context share its global state: the globals declared by a
+/// script stay defined for the next ones, like in a REPL. This state can be saved with
+/// [`Context::snapshot`] and put back with [`Context::restore`].
 #[derive(Debug)]
 pub struct Context {
     /// realm holds both the global object and the environment
@@ -196,6 +229,18 @@ pub struct Context {
 
     /// Cached standard objects and their prototypes
     standard_objects: StandardObjects,
+
+    /// Whether the code evaluated at the top level is strict mode code.
+    strict: bool,
+
+    /// Non-fatal diagnostics found in the evaluated code.
+    warnings: Vec<Diagnostic>,
+
+    /// Flag to stop the execution, possibly from another thread.
+    interrupt: Arc<AtomicBool>,
+
+    /// Global state right after the intrinsics were created, put back by [`Context::clear`].
+    initial_state: Option<Snapshot>,
 }
 
 impl Default for Context {
@@ -212,6 +257,10 @@ impl Default for Context {
             well_known_symbols,
             iterator_prototypes: IteratorPrototypes::default(),
             standard_objects: Default::default(),
+            strict: false,
+            warnings: Vec::new(),
+            interrupt: Arc::default(),
+            initial_state: None,
         };
 
         // Add new builtIns to Context Realm
@@ -219,6 +268,7 @@ impl Default for Context {
         // but for now we almost always want these default builtins
         context.create_intrinsics();
         context.iterator_prototypes = IteratorPrototypes::init(&mut context);
+        context.initial_state = Some(context.snapshot());
         context
     }
 }
@@ -494,6 +544,25 @@ impl Context {
     }
 
     /// Register a global function.
+    ///
+    /// The function is bound as a property of the global object, with the given `name` and
+    /// `length` properties.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::{Context, Value};
+    /// let mut context = Context::new();
+    ///
+    /// context
+    ///     .register_global_function("double", 1, |_, args, context| {
+    ///         let number = args.get(0).cloned().unwrap_or_default().to_number(context)?;
+    ///         Ok(Value::from(number * 2.0))
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let value = context.eval("double(21)").unwrap();
+    /// assert_eq!(value.as_number().unwrap(), 42.0);
+    /// ```
     pub fn register_global_function(
         &mut self,
         name: &str,
@@ -505,6 +574,28 @@ impl Context {
         Ok(())
     }
 
+    /// Defines a global property with the given `name` and `value`.
+    ///
+    /// The property is writable and configurable, like the global properties that scripts create
+    /// themselves.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::{Context, Value};
+    /// let mut context = Context::new();
+    ///
+    /// context.set_global_property("answer", Value::from(42));
+    ///
+    /// let value = context.eval("answer / 2").unwrap();
+    /// assert_eq!(value.as_number().unwrap(), 21.0);
+    /// ```
+    pub fn set_global_property(&mut self, name: &str, value: Value) {
+        self.global_object()
+            .as_object_mut()
+            .unwrap()
+            .insert_property(name, value, Attribute::WRITABLE | Attribute::CONFIGURABLE);
+    }
+
     /// Converts an array object into a rust vector of values.
     ///
     /// This is useful for the spread operator, for any other object an `Err` is returned
@@ -617,6 +708,122 @@ impl Context {
         Ok(())
     }
 
+    /// Whether the code evaluated at the top level is strict mode code, even without a
+    /// `"use strict"` directive.
+    #[inline]
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets whether the code evaluated at the top level from now on is strict mode code, as if
+    /// it began with a `"use strict"` directive.
+    ///
+    /// Functions declared in that code are strict too, while a `"use strict"` directive still makes
+    /// a function strict in non-strict code. Boa does not keep track of the strictness of each
+    /// function at runtime yet, so the runtime checks of strict mode, like assigning to an
+    /// undeclared variable, apply to all the code that runs while this is set.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// context.set_strict(true);
+    /// assert!(context.eval("undeclared = 1").is_err());
+    ///
+    /// context.set_strict(false);
+    /// assert!(context.eval("undeclared = 1").is_ok());
+    /// ```
+    #[inline]
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Takes the non-fatal diagnostics found in the code evaluated so far, like the use of
+    /// deprecated octal escape sequences in non-strict code.
+    ///
+    /// Evaluation doesn't depend on them, but tools like linters can report them.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// context.eval(r#"let a = "\101";"#).unwrap();
+    ///
+    /// let warnings = context.take_warnings();
+    /// assert_eq!(warnings[0].message(), "octal escape sequences are deprecated");
+    /// assert!(context.take_warnings().is_empty());
+    /// ```
+    #[inline]
+    pub fn take_warnings(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Gets the flag that interrupts the execution of this context, which can be set from another
+    /// thread.
+    ///
+    /// While the flag is set, the running code stops at the next iteration of a loop or call of a
+    /// function, with a `RangeError` that `try` statements can't catch.
+    #[inline]
+    pub fn interrupt_handle(&self) -> Arc<AtomicBool> {
+        self.interrupt.clone()
+    }
+
+    /// Whether the execution is interrupted.
+    #[inline]
+    pub fn interrupted(&self) -> bool {
+        self.interrupt.load(Ordering::Relaxed)
+    }
+
+    /// Stops the execution with an error if it is interrupted.
+    #[inline]
+    pub(crate) fn check_interrupt(&mut self) -> Result<()> {
+        if self.interrupted() {
+            Err(self.construct_range_error("the execution was interrupted"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Evaluates the given code, interrupting it if it runs for longer than `timeout`.
+    ///
+    /// An interrupted evaluation returns a `RangeError` with the message
+    /// `the execution was interrupted`. The interrupt flag is cleared before returning.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    ///# use std::time::Duration;
+    /// let mut context = Context::new();
+    ///
+    /// let value = context.eval_with_timeout("1 + 3", Duration::from_secs(1)).unwrap();
+    /// assert_eq!(value.as_number().unwrap(), 4.0);
+    ///
+    /// let error = context
+    ///     .eval_with_timeout("while (true) {}", Duration::from_millis(10))
+    ///     .unwrap_err();
+    /// assert!(error.display().to_string().contains("the execution was interrupted"));
+    /// ```
+    pub fn eval_with_timeout(&mut self, src: &str, timeout: Duration) -> Result<Value> {
+        let (done, finished) = mpsc::channel::<()>();
+        let interrupt = self.interrupt_handle();
+        let timer = thread::spawn(move || {
+            // The sender is dropped once the evaluation is done.
+            if finished.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                interrupt.store(true, Ordering::Relaxed);
+            }
+        });
+
+        let result = self.eval(src);
+
+        drop(done);
+        timer.join().expect("the timer thread panicked");
+        self.interrupt.store(false, Ordering::Relaxed);
+
+        result
+    }
+
     /// Evaluates the given code.
     ///
     /// # Examples
@@ -629,13 +836,220 @@ impl Context {
     /// assert!(value.is_number());
     /// assert_eq!(value.as_number().unwrap(), 4.0);
     /// ```
-    #[allow(clippy::unit_arg, clippy::drop_copy)]
     pub fn eval(&mut self, src: &str) -> Result<Value> {
+        self.eval_parser(Parser::new(src.as_bytes()))
+    }
+
+    /// Evaluates the given code, using `name` as the name of the source in syntax errors.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// let error = context.eval_named("main.js", r#""\xZZ""#).unwrap_err();
+    ///
+    /// assert!(error.display().to_string().contains("main.js:1:"));
+    /// ```
+    pub fn eval_named(&mut self, name: &str, src: &str) -> Result<Value> {
+        let mut parser = Parser::new(src.as_bytes());
+        parser.set_source_name(name);
+
+        self.eval_parser(parser)
+    }
+
+    /// Evaluates the given code as a script or as a module, depending on its content.
+    ///
+    /// The code is a module if it has an `import` or `export` declaration at its top level.
+    /// Modules are not supported yet, so they throw a `SyntaxError` instead of being evaluated.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// let value = context.eval_script_or_module("1 + 3").unwrap();
+    /// assert_eq!(value.as_number().unwrap(), 4.0);
+    ///
+    /// assert!(context.eval_script_or_module("export const x = 1;").is_err());
+    /// ```
+    pub fn eval_script_or_module(&mut self, src: &str) -> Result<Value> {
+        if parser::uses_module_syntax(src.as_bytes()) {
+            self.throw_syntax_error("modules are not supported yet")
+        } else {
+            self.eval(src)
+        }
+    }
+
+    /// Evaluates the given code, converting the result to JSON.
+    ///
+    /// The conversion follows `JSON.stringify`: `toJSON` methods are called, and functions,
+    /// symbols and `undefined` are left out of objects and become `null` in arrays. They also
+    /// become `null` if they are the result itself.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// let json = context.eval_json("({ a: 1, b: [2, 3] })").unwrap();
+    ///
+    /// assert_eq!(json.to_string(), r#"{"a":1,"b":[2,3]}"#);
+    /// ```
+    pub fn eval_json(&mut self, src: &str) -> Result<JSONValue> {
+        let value = self.eval(src)?;
+
+        if value.is_undefined() || value.is_function() || value.is_symbol() {
+            Ok(JSONValue::Null)
+        } else {
+            value.to_json(self)
+        }
+    }
+
+    /// Parses the given code, without evaluating it.
+    ///
+    /// The resulting AST can be inspected, and evaluated any number of times with
+    /// [`Context::eval_ast`].
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// let ast = context.parse_to_ast("1 + 3").unwrap();
+    /// let value = context.eval_ast(&ast).unwrap();
+    ///
+    /// assert_eq!(value.as_number().unwrap(), 4.0);
+    /// ```
+    pub fn parse_to_ast(&self, src: &str) -> StdResult<StatementList, ParseError> {
+        let mut parser = Parser::new(src.as_bytes());
+        parser.set_strict_mode(self.strict);
+
+        parser.parse_all()
+    }
+
+    /// Evaluates an already parsed AST.
+    pub fn eval_ast(&mut self, ast: &StatementList) -> Result<Value> {
+        ast.run(self)
+    }
+
+    /// Evaluates the code read from the given source.
+    ///
+    /// The source is fed straight into the lexer, so it never needs to be held in memory as a
+    /// whole.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// let value = context.eval_reader("1 + 3".as_bytes()).unwrap();
+    ///
+    /// assert_eq!(value.as_number().unwrap(), 4.0);
+    /// ```
+    pub fn eval_reader<R>(&mut self, src: R) -> Result<Value>
+    where
+        R: Read,
+    {
+        self.eval_parser(Parser::new(src))
+    }
+
+    /// Saves the global state of the context, so it can be put back later with
+    /// [`Context::restore`].
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// context.eval("let x = 1").unwrap();
+    /// let snapshot = context.snapshot();
+    /// context.eval("x = 2").unwrap();
+    /// context.restore(snapshot);
+    ///
+    /// let value = context.eval("x").unwrap();
+    /// assert_eq!(value.as_number().unwrap(), 1.0);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        let global_properties = self
+            .global_object()
+            .as_object()
+            .expect("global object")
+            .snapshot_properties();
+        let global_environment = self
+            .realm
+            .environment
+            .get_global_environment()
+            .borrow()
+            .as_global()
+            .expect("global environment")
+            .clone();
+
+        Snapshot {
+            global_properties,
+            global_environment,
+        }
+    }
+
+    /// Puts back the global state saved by [`Context::snapshot`].
+    ///
+    /// The globals defined since the snapshot are removed, and the others get back the value
+    /// they had when it was made.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.global_object()
+            .as_object_mut()
+            .expect("global object")
+            .restore_properties(snapshot.global_properties);
+        *self.realm.environment.get_global_environment().borrow_mut() =
+            Box::new(snapshot.global_environment);
+    }
+
+    /// Resets the context to the state it had when it was created, keeping its intrinsic objects.
+    ///
+    /// The globals defined by the evaluated scripts are removed, and the strict mode, the warnings
+    /// and the interrupt flag are reset. This is much cheaper than creating a new context, but
+    /// the intrinsic objects are kept as they are: changes made to them, like a property added to
+    /// `Array.prototype`, are not undone.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// context.eval("var x = 1").unwrap();
+    /// context.clear();
+    ///
+    /// assert!(context.eval("x").is_err());
+    /// ```
+    pub fn clear(&mut self) {
+        let initial_state = self.initial_state.clone().expect("initial state not saved");
+        self.restore(initial_state);
+        // An error can leave the environments of the functions it went through behind.
+        while self.realm.environment.environments().nth(1).is_some() {
+            self.realm.environment.pop();
+        }
+
+        self.executor = Interpreter::new();
+        #[cfg(feature = "console")]
+        {
+            self.console = Console::default();
+        }
+        self.strict = false;
+        self.warnings.clear();
+        self.interrupt.store(false, Ordering::Relaxed);
+    }
+
+    /// Parses and runs everything the given parser reads.
+    #[allow(clippy::unit_arg, clippy::drop_copy)]
+    fn eval_parser<R>(&mut self, mut parser: Parser<R>) -> Result<Value>
+    where
+        R: Read,
+    {
         let main_timer = BoaProfiler::global().start_event("Main", "Main");
 
-        let parsing_result = Parser::new(src.as_bytes())
-            .parse_all()
-            .map_err(|e| e.to_string());
+        parser.set_strict_mode(self.strict);
+        let parsing_result = parser.parse_all().map_err(|e| e.to_string());
+        self.warnings.extend(parser.take_warnings());
 
         let execution_result = match parsing_result {
             Ok(statement_list) => statement_list.run(self),
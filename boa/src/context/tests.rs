@@ -0,0 +1,308 @@
+use super::*;
+use crate::syntax::ast::Position;
+
+#[test]
+fn eval_named_reports_source_name() {
+    let mut context = Context::new();
+
+    let error = context
+        .eval_named("broken.js", r#"let s = "\xZZ";"#)
+        .expect_err("invalid escape sequence not rejected as expected");
+
+    let message = error.display().to_string();
+    assert!(
+        message.contains("broken.js:1:14: invalid hexadecimal"),
+        "{}",
+        message
+    );
+    assert!(!message.contains("at position"), "{}", message);
+
+    // The errors of the parser report the source name too, with the position only once.
+    let error = context
+        .eval_named("broken.js", "let = 1;")
+        .expect_err("missing binding identifier not rejected as expected");
+
+    let message = error.display().to_string();
+    assert!(
+        message.contains("broken.js:1:5: expected token 'identifier', got '='"),
+        "{}",
+        message
+    );
+    assert!(!message.contains("at line"), "{}", message);
+}
+
+#[test]
+fn eval_throws_single_line_syntax_errors() {
+    let mut context = Context::new();
+
+    let error = context
+        .eval("let a = 1;\nlet x = \"unterminated")
+        .expect_err("unterminated string literal not rejected as expected");
+
+    let message = error.display().to_string();
+    assert!(!message.contains('\n'), "{}", message);
+}
+
+#[test]
+fn register_global_function() {
+    let mut context = Context::new();
+
+    context
+        .register_global_function("myfn", 0, |_, _, _| Ok(Value::from(42)))
+        .expect("could not register the global function");
+
+    assert_eq!(context.eval("myfn()").unwrap(), Value::from(42));
+    assert_eq!(context.eval("myfn.length").unwrap(), Value::from(0));
+    assert_eq!(context.eval("myfn.name").unwrap(), Value::from("myfn"));
+}
+
+#[test]
+fn set_global_property() {
+    let mut context = Context::new();
+
+    context.set_global_property("globalValue", Value::from(41));
+
+    assert_eq!(context.eval("globalValue + 1").unwrap(), Value::from(42));
+    assert_eq!(
+        context.eval("delete this.globalValue").unwrap(),
+        Value::from(true)
+    );
+}
+
+#[test]
+fn eval_json() {
+    let mut context = Context::new();
+
+    assert_eq!(
+        context.eval_json("({a:1,b:[2,3]})").unwrap(),
+        serde_json::json!({ "a": 1, "b": [2, 3] })
+    );
+    assert_eq!(
+        context
+            .eval_json("({a: 1, f: function() {}, u: undefined, g: [function() {}]})")
+            .unwrap(),
+        serde_json::json!({ "a": 1, "g": [null] })
+    );
+    assert_eq!(
+        context
+            .eval_json("({toJSON: function() { return 'json'; }})")
+            .unwrap(),
+        serde_json::json!("json")
+    );
+    assert_eq!(
+        context.eval_json("(function() {})").unwrap(),
+        serde_json::Value::Null
+    );
+}
+
+#[test]
+fn snapshot_and_restore() {
+    let mut context = Context::new();
+
+    context.eval("x = 1; let y = 1;").unwrap();
+    let snapshot = context.snapshot();
+    context
+        .eval("x = 2; y = 2; let z = 2; this.w = 2;")
+        .unwrap();
+    context.restore(snapshot.clone());
+
+    assert_eq!(context.eval("x === 1").unwrap(), Value::from(true));
+    assert_eq!(context.eval("y === 1").unwrap(), Value::from(true));
+    assert!(context.eval("z").is_err());
+    assert!(context.eval("w").is_err());
+
+    // The same snapshot can be restored again.
+    context.eval("x = 3").unwrap();
+    context.restore(snapshot);
+    assert_eq!(context.eval("x === 1").unwrap(), Value::from(true));
+}
+
+#[test]
+fn clear() {
+    let mut context = Context::new();
+    context.set_strict(true);
+
+    context
+        .eval("var x = 1; let y = 1; this.z = 1; Object.w = 1;")
+        .unwrap();
+    context.clear();
+
+    assert!(!context.strict());
+    assert!(context.eval("x").is_err());
+    assert!(context.eval("y").is_err());
+    assert!(context.eval("z").is_err());
+    assert_eq!(
+        context
+            .eval("Object.getPrototypeOf({}) === Object.prototype")
+            .unwrap(),
+        Value::from(true)
+    );
+    // The intrinsics are kept as they are.
+    assert_eq!(context.eval("Object.w").unwrap(), Value::from(1));
+
+    // The names can be declared again.
+    context.eval("let y = 2;").unwrap();
+    assert_eq!(context.eval("y").unwrap(), Value::from(2));
+}
+
+#[test]
+fn eval_script_or_module() {
+    let mut context = Context::new();
+
+    let value = context
+        .eval_script_or_module("let x = 1; x + 2")
+        .expect("could not evaluate the script");
+    assert_eq!(value, Value::from(3));
+
+    let error = context
+        .eval_script_or_module("export const y = 1;")
+        .expect_err("module not rejected as expected");
+    assert!(error
+        .display()
+        .to_string()
+        .contains("modules are not supported yet"));
+    assert!(context.eval("y").is_err());
+}
+
+#[test]
+fn strict_assignment_to_undeclared_variable() {
+    let mut context = Context::new();
+    context.set_strict(true);
+
+    let error = context
+        .eval("undeclared = 1;")
+        .expect_err("assignment to an undeclared variable not rejected as expected");
+    assert_eq!(
+        error.display().to_string(),
+        "\"ReferenceError\": \"undeclared is not defined\""
+    );
+    assert!(context.eval("undeclared").is_err());
+
+    context.eval("var declared;").unwrap();
+    assert_eq!(context.eval("declared = 1;").unwrap(), Value::from(1));
+
+    context.set_strict(false);
+    assert_eq!(context.eval("undeclared = 1;").unwrap(), Value::from(1));
+    assert_eq!(context.eval("undeclared").unwrap(), Value::from(1));
+}
+
+#[test]
+fn strict_parsing() {
+    let mut context = Context::new();
+    context.set_strict(true);
+
+    assert!(context.parse_to_ast("var x; delete x;").is_err());
+    assert!(context.eval("var x; delete x;").is_err());
+    assert!(context.eval("function f() { delete x; }").is_err());
+
+    // Without the default, only the functions with a directive are strict.
+    context.set_strict(false);
+    assert!(context.eval("var x; delete x;").is_ok());
+    assert!(context
+        .eval("function f() { \"use strict\"; delete x; }")
+        .is_err());
+    assert!(context
+        .eval("function g() { \"use strict\"; } delete x;")
+        .is_ok());
+}
+
+#[test]
+fn octal_escape_warning() {
+    let mut context = Context::new();
+
+    let value = context
+        .eval("let x = 1;\nlet a = \"A is \\101\";")
+        .expect("could not evaluate the code");
+    assert_eq!(value, Value::undefined());
+    assert_eq!(context.eval("a").unwrap(), Value::from("A is A"));
+
+    let warnings = context.take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].message(),
+        "octal escape sequences are deprecated"
+    );
+    assert_eq!(warnings[0].position(), Position::new(2, 15));
+    assert!(context.take_warnings().is_empty());
+
+    context.eval("\"\\0 and \\x41\"").unwrap();
+    assert!(context.take_warnings().is_empty());
+}
+
+#[test]
+fn eval_with_timeout() {
+    let mut context = Context::new();
+
+    for src in [
+        "while (true) {}",
+        "for (;;) {}",
+        "do {} while (true)",
+        "try { while (true) {} } catch (e) {}",
+    ]
+    .iter()
+    {
+        let error = context
+            .eval_with_timeout(src, Duration::from_millis(20))
+            .expect_err("the evaluation was not interrupted");
+        assert_eq!(
+            error.display().to_string(),
+            "\"RangeError\": \"the execution was interrupted\"",
+            "{}",
+            src
+        );
+        assert!(!context.interrupted());
+    }
+
+    let value = context
+        .eval_with_timeout(
+            "let x = 0; while (x < 10) { x++ } x",
+            Duration::from_secs(60),
+        )
+        .expect("the evaluation was interrupted");
+    assert_eq!(value, Value::from(10));
+}
+
+#[test]
+fn interrupt_handle() {
+    let mut context = Context::new();
+    context.eval("function f() { return 1; }").unwrap();
+
+    context.interrupt_handle().store(true, Ordering::Relaxed);
+    assert!(context.interrupted());
+    assert!(context.eval("f()").is_err());
+    assert!(context.eval("for (const x of [1]) {}").is_err());
+    assert_eq!(context.eval("1 + 1").unwrap(), Value::from(2));
+
+    context.interrupt_handle().store(false, Ordering::Relaxed);
+    assert_eq!(context.eval("f()").unwrap(), Value::from(1));
+}
+
+#[test]
+fn eval_reader() {
+    let mut context = Context::new();
+
+    let value = context
+        .eval_reader(std::io::Cursor::new(&b"1 + 2"[..]))
+        .expect("could not evaluate the code");
+
+    assert_eq!(value, Value::from(3));
+}
+
+#[test]
+fn parse_to_ast_and_eval_ast() {
+    let mut context = Context::new();
+
+    let ast = context
+        .parse_to_ast("x = 1;")
+        .expect("could not parse the code");
+
+    if let [Node::Assign(assign)] = ast.statements() {
+        assert_eq!(assign.lhs(), &Node::from(Identifier::from("x")));
+        assert_eq!(assign.rhs(), &Node::from(Const::from(1)));
+    } else {
+        panic!("unexpected AST: {:?}", ast);
+    }
+
+    assert_eq!(context.eval_ast(&ast).unwrap(), Value::from(1));
+    assert_eq!(context.eval("x").unwrap(), Value::from(1));
+}
@@ -9,7 +9,10 @@
 //! There are 5 Environment record kinds. They all have methods in common, these are implemented as a the `EnvironmentRecordTrait`
 //!
 use crate::{
-    environment::lexical_environment::{Environment, EnvironmentType},
+    environment::{
+        global_environment_record::GlobalEnvironmentRecord,
+        lexical_environment::{Environment, EnvironmentType},
+    },
     Value,
 };
 use gc::{Finalize, Trace};
@@ -82,4 +85,9 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
 
     /// Fetch global variable
     fn get_global_object(&self) -> Option<Value>;
+
+    /// Return this Environment Record if it is a global Environment Record
+    fn as_global(&self) -> Option<&GlobalEnvironmentRecord> {
+        None
+    }
 }
@@ -193,4 +193,8 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
     fn get_global_object(&self) -> Option<Value> {
         Some(self.global_this_binding.clone())
     }
+
+    fn as_global(&self) -> Option<&GlobalEnvironmentRecord> {
+        Some(self)
+    }
 }
@@ -105,6 +105,12 @@ impl LexicalEnvironment {
         self.environment_stack.iter().rev()
     }
 
+    pub fn get_global_environment(&self) -> &Environment {
+        self.environment_stack
+            .get(0)
+            .expect("there should always be a global environment")
+    }
+
     pub fn get_global_object(&self) -> Option<Value> {
         self.environment_stack
             .get(0)
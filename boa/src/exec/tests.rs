@@ -1517,3 +1517,33 @@ fn test_strict_mode_dup_func_parameters() {
 
     assert!(string.starts_with("Uncaught \"SyntaxError\": "));
 }
+
+#[test]
+fn template_literal() {
+    let scenario = r#"
+        var name = "world";
+        `hello ${name}, ${1 + 1} times\n${`nested ${name}`}`;
+        "#;
+
+    assert_eq!(&exec(scenario), "\"hello world, 2 times\nnested world\"");
+}
+
+#[test]
+fn template_literal_line_continuation() {
+    let scenario = "`a\\\nb`;";
+
+    assert_eq!(&exec(scenario), "\"ab\"");
+}
+
+#[test]
+fn template_literal_converts_substitutions_to_strings() {
+    let scenario = r#"
+        var obj = {
+            toString: function() { return "string"; },
+            valueOf: function() { return "value"; }
+        };
+        `${obj}|${null}|${[1, 2]}`;
+        "#;
+
+    assert_eq!(&exec(scenario), "\"string|null|1,2\"");
+}
@@ -178,7 +178,7 @@ impl GcObject {
         match f_body {
             FunctionBody::BuiltIn(func) => func(this, args, ctx),
             FunctionBody::Ordinary(body) => {
-                let result = body.run(ctx);
+                let result = ctx.check_interrupt().and_then(|()| body.run(ctx));
                 ctx.realm_mut().environment.pop();
 
                 result
@@ -262,6 +262,7 @@ impl GcObject {
                 Ok(this)
             }
             FunctionBody::Ordinary(body) => {
+                ctx.check_interrupt()?;
                 let _ = body.run(ctx);
 
                 // local_env gets dropped here, its no longer needed
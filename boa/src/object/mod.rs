@@ -70,6 +70,15 @@ pub struct Object {
     extensible: bool,
 }
 
+/// A copy of the own properties of an object.
+#[derive(Debug, Clone)]
+pub(crate) struct PropertiesSnapshot {
+    indexed_properties: FxHashMap<u32, PropertyDescriptor>,
+    string_properties: FxHashMap<RcString, PropertyDescriptor>,
+    symbol_properties: FxHashMap<RcSymbol, PropertyDescriptor>,
+    extensible: bool,
+}
+
 /// Defines the different types of objects.
 #[derive(Debug, Trace, Finalize)]
 pub enum ObjectData {
@@ -466,6 +475,27 @@ impl Object {
         object
     }
 
+    /// Copies the own properties of the object, so they can be put back with
+    /// [`restore_properties`](Object::restore_properties).
+    ///
+    /// The values are not deep-copied, objects held by the properties are shared.
+    pub(crate) fn snapshot_properties(&self) -> PropertiesSnapshot {
+        PropertiesSnapshot {
+            indexed_properties: self.indexed_properties.clone(),
+            string_properties: self.string_properties.clone(),
+            symbol_properties: self.symbol_properties.clone(),
+            extensible: self.extensible,
+        }
+    }
+
+    /// Replaces the own properties of the object by the ones of a snapshot.
+    pub(crate) fn restore_properties(&mut self, snapshot: PropertiesSnapshot) {
+        self.indexed_properties = snapshot.indexed_properties;
+        self.string_properties = snapshot.string_properties;
+        self.symbol_properties = snapshot.symbol_properties;
+        self.extensible = snapshot.extensible;
+    }
+
     /// Returns `true` if it holds an Rust type that implements `NativeObject`.
     #[inline]
     pub fn is_native_object(&self) -> bool {
@@ -95,6 +95,7 @@ impl Executable for DoWhileLoop {
         }
 
         while self.cond().run(interpreter)?.to_boolean() {
+            interpreter.check_interrupt()?;
             result = self.body().run(interpreter)?;
             match interpreter.executor().get_current_state() {
                 InterpreterState::Break(_label) => {
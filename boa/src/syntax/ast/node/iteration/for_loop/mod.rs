@@ -118,6 +118,7 @@ impl Executable for ForLoop {
             .transpose()?
             .unwrap_or(true)
         {
+            interpreter.check_interrupt()?;
             let result = self.body().run(interpreter)?;
 
             match interpreter.executor().get_current_state() {
@@ -72,6 +72,7 @@ impl Executable for ForOfLoop {
         let mut result = Value::undefined();
 
         loop {
+            interpreter.check_interrupt()?;
             {
                 let env = &mut interpreter.realm_mut().environment;
                 env.push(new_declarative_environment(Some(
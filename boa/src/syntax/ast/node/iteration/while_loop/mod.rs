@@ -68,6 +68,7 @@ impl Executable for WhileLoop {
     fn run(&self, interpreter: &mut Context) -> Result<Value> {
         let mut result = Value::undefined();
         while self.cond().run(interpreter)?.to_boolean() {
+            interpreter.check_interrupt()?;
             result = self.expr().run(interpreter)?;
             match interpreter.executor().get_current_state() {
                 InterpreterState::Break(label) => {
@@ -16,6 +16,7 @@ pub mod return_smt;
 pub mod spread;
 pub mod statement_list;
 pub mod switch;
+pub mod template;
 pub mod throw;
 pub mod try_node;
 
@@ -39,6 +40,7 @@ pub use self::{
     spread::Spread,
     statement_list::{RcStatementList, StatementList},
     switch::{Case, Switch},
+    template::{TemplateElement, TemplateLit},
     throw::Throw,
     try_node::{Catch, Finally, Try},
 };
@@ -143,6 +145,9 @@ pub enum Node {
     /// A spread (...x) statement. [More information](./spread/struct.Spread.html).
     Spread(Spread),
 
+    /// A template literal. [More information](./template/struct.TemplateLit.html).
+    TemplateLit(TemplateLit),
+
     /// A throw statement. [More information](./throw/struct.Throw.html).
     Throw(Throw),
 
@@ -221,6 +226,7 @@ impl Node {
             Self::Break(ref break_smt) => Display::fmt(break_smt, f),
             Self::Continue(ref cont) => Display::fmt(cont, f),
             Self::Spread(ref spread) => Display::fmt(spread, f),
+            Self::TemplateLit(ref template) => Display::fmt(template, f),
             Self::Block(ref block) => block.display(f, indentation),
             Self::Identifier(ref s) => Display::fmt(s, f),
             Self::New(ref expr) => Display::fmt(expr, f),
@@ -290,6 +296,7 @@ impl Executable for Node {
             Node::LetDeclList(ref decl) => decl.run(interpreter),
             Node::ConstDeclList(ref decl) => decl.run(interpreter),
             Node::Spread(ref spread) => spread.run(interpreter),
+            Node::TemplateLit(ref template) => template.run(interpreter),
             Node::This => {
                 // Will either return `this` binding or undefined
                 Ok(interpreter.realm().environment.get_this_binding())
@@ -56,11 +56,15 @@ impl Executable for Assign {
         let val = self.rhs().run(interpreter)?;
         match self.lhs() {
             Node::Identifier(ref name) => {
+                let strict = interpreter.strict();
                 let environment = &mut interpreter.realm_mut().environment;
 
                 if environment.has_binding(name.as_ref()) {
                     // Binding already exists
                     environment.set_mutable_binding(name.as_ref(), val.clone(), true);
+                } else if strict {
+                    // Strict mode code can't create global variables by assigning to them.
+                    return interpreter.throw_reference_error(name.as_ref());
                 } else {
                     environment.create_mutable_binding(
                         name.as_ref().to_owned(),
@@ -0,0 +1,99 @@
+//! Template literal node.
+
+use super::Node;
+use crate::{exec::Executable, BoaProfiler, Context, Result, Value};
+use gc::{Finalize, Trace};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Template literals are string literals allowing embedded expressions.
+///
+/// The value of a template literal is the concatenation of its strings and of the values of its
+/// substitutions, converted to strings.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-template-literals
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+pub struct TemplateLit {
+    elements: Box<[TemplateElement]>,
+}
+
+impl TemplateLit {
+    /// Creates a new `TemplateLit` AST node.
+    pub fn new<E>(elements: E) -> Self
+    where
+        E: Into<Box<[TemplateElement]>>,
+    {
+        Self {
+            elements: elements.into(),
+        }
+    }
+
+    /// Gets the strings and substitutions of the template literal, in source order.
+    pub fn elements(&self) -> &[TemplateElement] {
+        &self.elements
+    }
+}
+
+impl Executable for TemplateLit {
+    fn run(&self, interpreter: &mut Context) -> Result<Value> {
+        let _timer = BoaProfiler::global().start_event("TemplateLiteral", "exec");
+        let mut result = String::new();
+
+        for element in self.elements.iter() {
+            match element {
+                TemplateElement::String(s) => result.push_str(s),
+                TemplateElement::Expr(node) => {
+                    let value = node.run(interpreter)?;
+                    result.push_str(&value.to_string(interpreter)?);
+                }
+            }
+        }
+
+        Ok(result.into())
+    }
+}
+
+impl fmt::Display for TemplateLit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("`")?;
+        for element in self.elements.iter() {
+            match element {
+                TemplateElement::String(s) => {
+                    let mut chars = s.chars().peekable();
+                    while let Some(ch) = chars.next() {
+                        match ch {
+                            '`' | '\\' => write!(f, "\\{}", ch)?,
+                            '$' if chars.peek() == Some(&'{') => f.write_str("\\$")?,
+                            _ => write!(f, "{}", ch)?,
+                        }
+                    }
+                }
+                TemplateElement::Expr(node) => write!(f, "${{{}}}", node)?,
+            }
+        }
+        f.write_str("`")
+    }
+}
+
+impl From<TemplateLit> for Node {
+    fn from(template: TemplateLit) -> Self {
+        Self::TemplateLit(template)
+    }
+}
+
+/// A part of a template literal: either a string, with its escape sequences already decoded, or
+/// a substitution.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+pub enum TemplateElement {
+    String(Box<str>),
+    Expr(Node),
+}
@@ -96,7 +96,9 @@ impl Executable for Try {
         let _timer = BoaProfiler::global().start_event("Try", "exec");
         let res = self.block().run(interpreter).map_or_else(
             |err| {
-                if let Some(catch) = self.catch() {
+                // An interrupted execution can't be caught.
+                let catch = self.catch().filter(|_| !interpreter.interrupted());
+                if let Some(catch) = catch {
                     {
                         let env = &mut interpreter.realm_mut().environment;
                         env.push(new_declarative_environment(Some(
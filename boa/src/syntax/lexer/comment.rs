@@ -1,6 +1,6 @@
 //! This module implements lexing for comments used in the JavaScript programing language.
 
-use super::{Cursor, Error, Tokenizer};
+use super::{is_line_terminator, Cursor, Error, Tokenizer};
 use crate::{
     profiler::BoaProfiler,
     syntax::{
@@ -31,7 +31,7 @@ impl<R> Tokenizer<R> for SingleLineComment {
 
         // Skip either to the end of the line or to the end of the input
         while let Some(ch) = cursor.peek()? {
-            if ch == '\n' {
+            if is_line_terminator(ch) {
                 break;
             } else {
                 // Consume char.
@@ -69,7 +69,7 @@ impl<R> Tokenizer<R> for MultiLineComment {
             if let Some(ch) = cursor.next_char()? {
                 if ch == '*' && cursor.next_is('/')? {
                     break;
-                } else if ch == '\n' {
+                } else if is_line_terminator(ch) {
                     new_line = true;
                 }
             } else {
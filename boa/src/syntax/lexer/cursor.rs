@@ -1,14 +1,26 @@
 //! Module implementing the lexer cursor. This is used for managing the input byte stream.
 
+use super::Diagnostic;
 use crate::{profiler::BoaProfiler, syntax::ast::Position};
-use std::io::{self, Bytes, Error, ErrorKind, Read};
+use std::{
+    io::{self, Bytes, Error, Read},
+    mem,
+};
 
 /// Cursor over the source code.
 #[derive(Debug)]
 pub(super) struct Cursor<R> {
     iter: InnerIter<R>,
     pos: Position,
+    column_unit: ColumnUnit,
     strict_mode: bool,
+    /// Whether malformed escape sequences are replaced instead of being rejected.
+    recover: bool,
+    source_name: Option<Box<str>>,
+    /// Source text consumed since the recording started, if it did.
+    recording: Option<String>,
+    /// Non-fatal diagnostics found so far.
+    warnings: Vec<Diagnostic>,
 }
 
 impl<R> Cursor<R> {
@@ -20,8 +32,14 @@ impl<R> Cursor<R> {
     /// Advances the position to the next column.
     #[inline]
     pub(super) fn next_column(&mut self) {
+        self.advance_columns(1);
+    }
+
+    /// Advances the position by the given number of columns.
+    #[inline]
+    fn advance_columns(&mut self, columns: u32) {
         let current_line = self.pos.line_number();
-        let next_column = self.pos.column_number() + 1;
+        let next_column = self.pos.column_number() + columns;
         self.pos = Position::new(current_line, next_column);
     }
 
@@ -32,6 +50,18 @@ impl<R> Cursor<R> {
         self.pos = Position::new(next_line, 1);
     }
 
+    /// Gets how the columns of the positions are counted.
+    #[inline]
+    pub(super) fn column_unit(&self) -> ColumnUnit {
+        self.column_unit
+    }
+
+    /// Sets how the columns of the positions are counted.
+    #[inline]
+    pub(super) fn set_column_unit(&mut self, column_unit: ColumnUnit) {
+        self.column_unit = column_unit
+    }
+
     #[inline]
     pub(super) fn strict_mode(&self) -> bool {
         self.strict_mode
@@ -41,6 +71,77 @@ impl<R> Cursor<R> {
     pub(super) fn set_strict_mode(&mut self, strict_mode: bool) {
         self.strict_mode = strict_mode
     }
+
+    /// Whether malformed escape sequences are replaced instead of being rejected.
+    #[inline]
+    pub(super) fn recover(&self) -> bool {
+        self.recover
+    }
+
+    /// Sets whether malformed escape sequences are replaced instead of being rejected.
+    #[inline]
+    pub(super) fn set_recover(&mut self, recover: bool) {
+        self.recover = recover
+    }
+
+    /// Gets the name of the source being read, if any.
+    #[inline]
+    pub(super) fn source_name(&self) -> Option<&str> {
+        self.source_name.as_deref()
+    }
+
+    /// Skips the byte order mark (U+FEFF) the source may start with, so that its first character
+    /// is at the start of the first line. It must be called before anything is read.
+    ///
+    /// A byte order mark anywhere else is kept, it's a whitespace.
+    #[inline]
+    pub(super) fn skip_bom(&mut self) {
+        self.iter.skip_bom = true;
+    }
+
+    /// Sets the name of the source being read, used to report error positions.
+    #[inline]
+    pub(super) fn set_source_name<N>(&mut self, name: N)
+    where
+        N: Into<Box<str>>,
+    {
+        self.source_name = Some(name.into())
+    }
+
+    /// Reports a non-fatal diagnostic at the given position.
+    #[inline]
+    pub(super) fn warn<M>(&mut self, message: M, pos: Position)
+    where
+        M: Into<Box<str>>,
+    {
+        self.warnings.push(Diagnostic::new(message, pos))
+    }
+
+    /// Takes the diagnostics reported so far.
+    #[inline]
+    pub(super) fn take_warnings(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Starts recording the source text consumed by the cursor.
+    #[inline]
+    pub(super) fn start_recording(&mut self) {
+        self.recording = Some(String::new());
+    }
+
+    /// Stops recording the source text, returning what was consumed since the recording started.
+    #[inline]
+    pub(super) fn take_recording(&mut self) -> String {
+        self.recording.take().unwrap_or_default()
+    }
+
+    /// Adds a consumed character to the recording, if there is one.
+    #[inline]
+    fn record(&mut self, ch: char) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(ch);
+        }
+    }
 }
 
 impl<R> Cursor<R>
@@ -53,7 +154,12 @@ where
         Self {
             iter: InnerIter::new(inner.bytes()),
             pos: Position::new(1, 1),
+            column_unit: ColumnUnit::default(),
             strict_mode: false,
+            recover: false,
+            source_name: None,
+            recording: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -73,6 +179,7 @@ where
         Ok(match self.peek()? {
             Some(next) if next == peek => {
                 let _ = self.iter.next_char();
+                self.record(next);
                 true
             }
             _ => false,
@@ -97,26 +204,6 @@ where
         })
     }
 
-    /// Fills the buffer with all characters until the stop character is found.
-    ///
-    /// Note: It will not add the stop character to the buffer.
-    pub(super) fn take_until(&mut self, stop: char, buf: &mut String) -> io::Result<()> {
-        let _timer = BoaProfiler::global().start_event("cursor::take_until()", "Lexing");
-
-        loop {
-            if self.next_is(stop)? {
-                return Ok(());
-            } else if let Some(ch) = self.next_char()? {
-                buf.push(ch);
-            } else {
-                return Err(io::Error::new(
-                    ErrorKind::UnexpectedEof,
-                    format!("Unexpected end of file when looking for character {}", stop),
-                ));
-            }
-        }
-    }
-
     /// Fills the buffer with characters until the first character (x) for which the predicate (pred) is false
     /// (or the next character is none).
     ///
@@ -139,17 +226,6 @@ where
         }
     }
 
-    /// It will fill the buffer with checked ASCII bytes.
-    ///
-    /// This expects for the buffer to be fully filled. If it's not, it will fail with an
-    /// `UnexpectedEof` I/O error.
-    #[inline]
-    pub(super) fn fill_bytes(&mut self, buf: &mut [u8]) -> io::Result<()> {
-        let _timer = BoaProfiler::global().start_event("cursor::fill_bytes()", "Lexing");
-
-        self.iter.fill_bytes(buf)
-    }
-
     /// Retrieves the next UTF-8 character.
     #[inline]
     pub(crate) fn next_char(&mut self) -> Result<Option<char>, Error> {
@@ -161,13 +237,21 @@ where
             Some('\r') => {
                 // Try to take a newline if it's next, for windows "\r\n" newlines
                 // Otherwise, treat as a Mac OS9 bare '\r' newline
+                self.record('\r');
                 if self.peek()? == Some('\n') {
                     let _ = self.iter.next_char();
+                    self.record('\n');
                 }
                 self.next_line();
             }
-            Some('\n') | Some('\u{2028}') | Some('\u{2029}') => self.next_line(),
-            Some(_) => self.next_column(),
+            Some(ch @ '\n') | Some(ch @ '\u{2028}') | Some(ch @ '\u{2029}') => {
+                self.record(ch);
+                self.next_line()
+            }
+            Some(ch) => {
+                self.record(ch);
+                self.advance_columns(self.column_unit.width(ch))
+            }
             None => {}
         }
 
@@ -175,11 +259,47 @@ where
     }
 }
 
+/// The unit in which the columns of the positions in the source code are counted.
+///
+/// Tools showing the positions of errors may count columns differently, so the lexer can match
+/// them. Line terminators always start a new line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnUnit {
+    /// Each Unicode code point is a column. This is the default.
+    CodePoint,
+    /// Each UTF-16 code unit is a column, like in browser developer tools, so the characters
+    /// outside of the Basic Multilingual Plane take two columns.
+    Utf16,
+    /// Each byte of the UTF-8 source is a column.
+    Byte,
+}
+
+impl ColumnUnit {
+    /// Gets the number of columns taken by the given character.
+    #[inline]
+    fn width(self, ch: char) -> u32 {
+        match self {
+            Self::CodePoint => 1,
+            Self::Utf16 => ch.len_utf16() as u32,
+            Self::Byte => ch.len_utf8() as u32,
+        }
+    }
+}
+
+impl Default for ColumnUnit {
+    #[inline]
+    fn default() -> Self {
+        Self::CodePoint
+    }
+}
+
 /// Inner iterator for a cursor.
 #[derive(Debug)]
 struct InnerIter<R> {
     iter: Bytes<R>,
     peeked_char: Option<Option<char>>,
+    /// Whether the next character is skipped if it's a byte order mark.
+    skip_bom: bool,
 }
 
 impl<R> InnerIter<R> {
@@ -189,6 +309,7 @@ impl<R> InnerIter<R> {
         Self {
             iter,
             peeked_char: None,
+            skip_bom: false,
         }
     }
 }
@@ -197,23 +318,6 @@ impl<R> InnerIter<R>
 where
     R: Read,
 {
-    /// It will fill the buffer with checked ASCII bytes.
-    ///
-    /// This expects for the buffer to be fully filled. If it's not, it will fail with an
-    /// `UnexpectedEof` I/O error.
-    #[inline]
-    fn fill_bytes(&mut self, buf: &mut [u8]) -> io::Result<()> {
-        for byte in buf.iter_mut() {
-            *byte = self.next_ascii()?.ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::UnexpectedEof,
-                    "unexpected EOF when filling buffer",
-                )
-            })?;
-        }
-        Ok(())
-    }
-
     /// Peeks the next UTF-8 checked character.
     #[inline]
     pub(super) fn peek_char(&mut self) -> Result<Option<char>, Error> {
@@ -226,13 +330,24 @@ where
         }
     }
 
-    /// Retrieves the next UTF-8 checked character.
+    /// Retrieves the next UTF-8 checked character, skipping the leading byte order mark if asked
+    /// to.
     fn next_char(&mut self) -> io::Result<Option<char>> {
         if let Some(v) = self.peeked_char {
             let _ = self.peeked_char.take();
             return Ok(v);
         }
 
+        let chr = self.decode_char()?;
+        if mem::take(&mut self.skip_bom) && chr == Some('\u{FEFF}') {
+            return self.decode_char();
+        }
+
+        Ok(chr)
+    }
+
+    /// Decodes the next UTF-8 checked character from the bytes.
+    fn decode_char(&mut self) -> io::Result<Option<char>> {
         let first_byte = match self.iter.next().transpose()? {
             Some(b) => b,
             None => return Ok(None),
@@ -288,17 +403,4 @@ where
 
         Ok(Some(chr))
     }
-
-    /// Retrieves the next ASCII checked character.
-    #[inline]
-    fn next_ascii(&mut self) -> io::Result<Option<u8>> {
-        match self.next_char() {
-            Ok(Some(chr)) if chr.is_ascii() => Ok(Some(chr as u8)),
-            Ok(None) => Ok(None),
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "non-ASCII byte found",
-            )),
-        }
-    }
 }
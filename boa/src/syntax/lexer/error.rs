@@ -21,6 +21,9 @@ pub enum Error {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-native-error-types-used-in-this-standard-syntaxerror
     Syntax(Box<str>, Position),
+
+    /// An error in a named source, such as a file, along with the name of the source.
+    Named { name: Box<str>, err: Box<Error> },
 }
 
 impl From<io::Error> for Error {
@@ -38,6 +41,29 @@ impl Error {
     {
         Self::Syntax(err.into(), pos.into())
     }
+
+    /// Gets the position of the error in the source, if it's a syntax error.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Syntax(_, pos) => Some(*pos),
+            Self::Named { err, .. } => err.position(),
+            Self::IO(_) => None,
+        }
+    }
+
+    /// Attaches the name of the source to a syntax error, which is then formatted as
+    /// `<name>:<line>:<column>: <message>`.
+    ///
+    /// An error that already has a source name keeps it.
+    pub(crate) fn with_source_name(self, name: &str) -> Self {
+        match self {
+            Self::Syntax(..) => Self::Named {
+                name: name.into(),
+                err: Box::new(self),
+            },
+            e => e,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -45,6 +71,10 @@ impl fmt::Display for Error {
         match self {
             Self::IO(e) => write!(f, "I/O error: {}", e),
             Self::Syntax(e, pos) => write!(f, "Syntax Error: {} at position: {}", e, pos),
+            Self::Named { name, err } => match &**err {
+                Self::Syntax(e, pos) => write!(f, "Syntax Error: {}:{}: {}", name, pos, e),
+                err => write!(f, "{}: {}", name, err),
+            },
         }
     }
 }
@@ -54,6 +84,45 @@ impl StdError for Error {
         match self {
             Self::IO(err) => Some(err),
             Self::Syntax(_, _) => None,
+            Self::Named { err, .. } => err.source(),
         }
     }
 }
+
+/// A non-fatal diagnostic about the source code, like the use of a deprecated feature.
+///
+/// Diagnostics don't stop the lexing, they are collected for tools like linters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    message: Box<str>,
+    pos: Position,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic.
+    pub(super) fn new<M>(message: M, pos: Position) -> Self
+    where
+        M: Into<Box<str>>,
+    {
+        Self {
+            message: message.into(),
+            pos,
+        }
+    }
+
+    /// Gets the message of the diagnostic.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the position in the source the diagnostic is about.
+    pub fn position(&self) -> Position {
+        self.pos
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Warning: {} at position: {}", self.message, self.pos)
+    }
+}
@@ -1,6 +1,6 @@
 //! This module implements lexing for identifiers (foo, myvar, etc.) used in the JavaScript programing language.
 
-use super::{Cursor, Error, Tokenizer};
+use super::{string::decode_unicode_escape, Cursor, Error, Tokenizer};
 use crate::{
     profiler::BoaProfiler,
     syntax::{
@@ -39,9 +39,37 @@ pub(super) struct Identifier {
 
 impl Identifier {
     /// Creates a new identifier/keyword lexer.
+    ///
+    /// `init` is the first character of the identifier, or the `\` of a Unicode escape sequence.
     pub(super) fn new(init: char) -> Self {
         Self { init }
     }
+
+    /// Checks if a character can start an identifier.
+    pub(super) fn is_identifier_start(ch: char) -> bool {
+        ch.is_alphabetic() || ch == '$' || ch == '_'
+    }
+
+    /// Checks if a character can be part of an identifier, after its first character.
+    fn is_identifier_part(ch: char) -> bool {
+        Self::is_identifier_start(ch) || ch.is_digit(10) || ch == '\u{200C}' || ch == '\u{200D}'
+    }
+
+    /// Decodes a `\uXXXX` or `\u{X..X}` escape sequence in an identifier, whose `\` has
+    /// already been consumed.
+    fn take_escape<R>(cursor: &mut Cursor<R>, backslash_pos: Position) -> Result<char, Error>
+    where
+        R: Read,
+    {
+        if cursor.next_is('u')? {
+            decode_unicode_escape(cursor, backslash_pos)
+        } else {
+            Err(Error::syntax(
+                "only Unicode escape sequences are allowed in identifiers",
+                backslash_pos,
+            ))
+        }
+    }
 }
 
 impl<R> Tokenizer<R> for Identifier {
@@ -51,11 +79,57 @@ impl<R> Tokenizer<R> for Identifier {
     {
         let _timer = BoaProfiler::global().start_event("Identifier", "Lexing");
 
-        let mut buf = self.init.to_string();
+        // Identifiers can contain Unicode escape sequences, as long as they decode to characters
+        // that are allowed where they appear.
+        let mut escaped = self.init == '\\';
+        let first = if escaped {
+            Self::take_escape(cursor, start_pos)?
+        } else {
+            self.init
+        };
+        if !Self::is_identifier_start(first) {
+            return Err(Error::syntax(
+                format!(
+                    "U+{:04X} is not allowed at the start of an identifier",
+                    u32::from(first)
+                ),
+                start_pos,
+            ));
+        }
+
+        let mut buf = first.to_string();
+        loop {
+            let pos = cursor.pos();
+            if cursor.next_is('\\')? {
+                escaped = true;
+                let ch = Self::take_escape(cursor, pos)?;
+                if !Self::is_identifier_part(ch) {
+                    return Err(Error::syntax(
+                        format!("U+{:04X} is not allowed in an identifier", u32::from(ch)),
+                        pos,
+                    ));
+                }
+                buf.push(ch);
+            } else if cursor.next_is_pred(&Self::is_identifier_part)? {
+                buf.push(cursor.next_char()?.expect("identifier character vanished"));
+            } else {
+                break;
+            }
+        }
 
-        cursor.take_while_pred(&mut buf, &|c: char| {
-            c.is_alphabetic() || c.is_digit(10) || c == '_'
-        })?;
+        // Keywords and literals can't be written with escape sequences, as they would then be
+        // identifiers that look like keywords.
+        let is_reserved =
+            matches!(buf.as_str(), "true" | "false" | "null") || buf.parse::<Keyword>().is_ok();
+        if escaped && is_reserved {
+            return Err(Error::syntax(
+                format!(
+                    "keyword '{}' must not contain Unicode escape sequences",
+                    buf
+                ),
+                start_pos,
+            ));
+        }
 
         let tk = match buf.as_str() {
             "true" => TokenKind::BooleanLiteral(true),
@@ -42,8 +42,10 @@ use self::{
 };
 use crate::syntax::ast::{Punctuator, Span};
 pub use crate::{profiler::BoaProfiler, syntax::ast::Position};
-pub use error::Error;
+pub use cursor::ColumnUnit;
+pub use error::{Diagnostic, Error};
 use std::io::Read;
+pub use template::TemplateString;
 pub use token::{Token, TokenKind};
 
 trait Tokenizer<R> {
@@ -58,6 +60,10 @@ trait Tokenizer<R> {
 pub struct Lexer<R> {
     cursor: Cursor<R>,
     goal_symbol: InputElement,
+    /// Number of unclosed `{` in each template literal substitution being lexed, innermost last.
+    ///
+    /// A `}` ends the innermost substitution when none of its braces are left open.
+    template_braces: Vec<usize>,
 }
 
 impl<R> Lexer<R> {
@@ -100,16 +106,40 @@ impl<R> Lexer<R> {
         self.cursor.set_strict_mode(strict_mode)
     }
 
-    /// Creates a new lexer.
+    /// Sets whether the lexer recovers from malformed `\x` and `\u` escape sequences in string
+    /// literals.
+    ///
+    /// When recovering, such an escape sequence produces a U+FFFD REPLACEMENT CHARACTER instead
+    /// of a syntax error, which suits tools like syntax highlighters. It is off by default, as
+    /// the specification requires the error.
+    #[inline]
+    pub fn set_recover(&mut self, recover: bool) {
+        self.cursor.set_recover(recover)
+    }
+
+    /// Sets how the columns of the token and error positions are counted.
+    ///
+    /// Columns count Unicode code points by default.
+    #[inline]
+    pub fn set_column_unit(&mut self, column_unit: ColumnUnit) {
+        self.cursor.set_column_unit(column_unit)
+    }
+
+    /// Takes the non-fatal diagnostics found so far, like the use of deprecated escape sequences.
+    #[inline]
+    pub fn take_warnings(&mut self) -> Vec<Diagnostic> {
+        self.cursor.take_warnings()
+    }
+
+    /// Creates a new lexer, with the default options.
+    ///
+    /// Use a [`LexerBuilder`] to choose other options.
     #[inline]
     pub fn new(reader: R) -> Self
     where
         R: Read,
     {
-        Self {
-            cursor: Cursor::new(reader),
-            goal_symbol: Default::default(),
-        }
+        LexerBuilder::new(reader).build()
     }
 
     // Handles lexing of a token starting '/' with the '/' already being consumed.
@@ -195,10 +225,12 @@ impl<R> Lexer<R> {
                 TokenKind::LineTerminator,
                 Span::new(start, self.cursor.pos()),
             )),
-            '"' | '\'' => StringLiteral::new(next_chr).lex(&mut self.cursor, start),
-            '`' => TemplateLiteral.lex(&mut self.cursor, start),
+            '"' | '\'' => {
+                StringLiteral::new(next_chr, self.strict_mode()).lex(&mut self.cursor, start)
+            }
+            '`' => TemplateLiteral::new(false).lex(&mut self.cursor, start),
             _ if next_chr.is_digit(10) => NumberLiteral::new(next_chr).lex(&mut self.cursor, start),
-            _ if next_chr.is_alphabetic() || next_chr == '$' || next_chr == '_' => {
+            _ if Identifier::is_identifier_start(next_chr) || next_chr == '\\' => {
                 Identifier::new(next_chr).lex(&mut self.cursor, start)
             }
             ';' => Ok(Token::new(
@@ -222,14 +254,28 @@ impl<R> Lexer<R> {
                 Punctuator::Comma.into(),
                 Span::new(start, self.cursor.pos()),
             )),
-            '{' => Ok(Token::new(
-                Punctuator::OpenBlock.into(),
-                Span::new(start, self.cursor.pos()),
-            )),
-            '}' => Ok(Token::new(
-                Punctuator::CloseBlock.into(),
-                Span::new(start, self.cursor.pos()),
-            )),
+            '{' => {
+                if let Some(open) = self.template_braces.last_mut() {
+                    *open += 1;
+                }
+                Ok(Token::new(
+                    Punctuator::OpenBlock.into(),
+                    Span::new(start, self.cursor.pos()),
+                ))
+            }
+            '}' if self.template_braces.last() == Some(&0) => {
+                self.template_braces.pop();
+                TemplateLiteral::new(true).lex(&mut self.cursor, start)
+            }
+            '}' => {
+                if let Some(open) = self.template_braces.last_mut() {
+                    *open -= 1;
+                }
+                Ok(Token::new(
+                    Punctuator::CloseBlock.into(),
+                    Span::new(start, self.cursor.pos()),
+                ))
+            }
             '[' => Ok(Token::new(
                 Punctuator::OpenBracket.into(),
                 Span::new(start, self.cursor.pos()),
@@ -243,6 +289,12 @@ impl<R> Lexer<R> {
                 Span::new(start, self.cursor.pos()),
             )),
             '/' => self.lex_slash_token(start),
+            '#' if start == Position::new(1, 1) && self.cursor.next_is('!')? => {
+                // A HashbangComment is only allowed at the very start of the source, and lasts
+                // until the end of the line, like a single line comment.
+                // <https://tc39.es/ecma262/#sec-hashbang>
+                SingleLineComment.lex(&mut self.cursor, start)
+            }
             '=' | '*' | '+' | '-' | '%' | '|' | '&' | '^' | '<' | '>' | '!' | '~' => {
                 Operator::new(next_chr).lex(&mut self.cursor, start)
             }
@@ -255,7 +307,15 @@ impl<R> Lexer<R> {
                 );
                 Err(Error::syntax(details, start))
             }
-        }?;
+        }
+        .map_err(|e| match self.cursor.source_name() {
+            Some(name) => e.with_source_name(name),
+            None => e,
+        })?;
+
+        if let TokenKind::TemplateHead(_) | TokenKind::TemplateMiddle(_) = token.kind() {
+            self.template_braces.push(0);
+        }
 
         if token.kind() == &TokenKind::Comment {
             // Skip comment
@@ -266,6 +326,113 @@ impl<R> Lexer<R> {
     }
 }
 
+/// Checks if a character is a line terminator.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-line-terminators
+fn is_line_terminator(ch: char) -> bool {
+    matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// Builder for a [`Lexer`] with other options than the default ones.
+///
+/// # Examples
+/// ```
+/// use boa::syntax::lexer::{LexerBuilder, TokenKind};
+///
+/// let mut lexer = LexerBuilder::new(&br#""\x4G""#[..]).recover(true).build();
+///
+/// let token = lexer.next().unwrap().unwrap();
+/// assert_eq!(token.kind(), &TokenKind::string_literal("\u{FFFD}", r#""\x4G""#));
+/// ```
+#[derive(Debug)]
+pub struct LexerBuilder<R> {
+    reader: R,
+    strict: bool,
+    recover: bool,
+    column_unit: ColumnUnit,
+    source_name: Option<Box<str>>,
+}
+
+impl<R> LexerBuilder<R> {
+    /// Creates a new builder for a lexer of the given source.
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            strict: false,
+            recover: false,
+            column_unit: ColumnUnit::default(),
+            source_name: None,
+        }
+    }
+
+    /// Specify whether the source is lexed as strict mode code, which forbids the legacy octal
+    /// literals and escape sequences.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Specify whether malformed `\x` and `\u` escape sequences in string literals produce a
+    /// U+FFFD REPLACEMENT CHARACTER instead of a syntax error.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn recover(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
+
+    /// Specify how the columns of the token and error positions are counted.
+    ///
+    /// The default is [`ColumnUnit::CodePoint`].
+    #[inline]
+    pub fn column_unit(mut self, column_unit: ColumnUnit) -> Self {
+        self.column_unit = column_unit;
+        self
+    }
+
+    /// Specify the name of the source, which is included in syntax errors.
+    ///
+    /// By default, the source has no name.
+    #[inline]
+    pub fn source_name<N>(mut self, name: N) -> Self
+    where
+        N: Into<Box<str>>,
+    {
+        self.source_name = Some(name.into());
+        self
+    }
+
+    /// Build the lexer.
+    #[inline]
+    pub fn build(self) -> Lexer<R>
+    where
+        R: Read,
+    {
+        let mut cursor = Cursor::new(self.reader);
+        cursor.skip_bom();
+        cursor.set_strict_mode(self.strict);
+        cursor.set_recover(self.recover);
+        cursor.set_column_unit(self.column_unit);
+        if let Some(name) = self.source_name {
+            cursor.set_source_name(name);
+        }
+
+        Lexer {
+            cursor,
+            goal_symbol: Default::default(),
+            template_braces: Vec::new(),
+        }
+    }
+}
+
 /// ECMAScript goal symbols.
 ///
 /// <https://tc39.es/ecma262/#sec-ecmascript-language-lexical-grammar>
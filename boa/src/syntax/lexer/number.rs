@@ -107,9 +107,87 @@ where
     }
 
     // Consume the decimal digits.
-    cursor.take_while_pred(buf, &|c: char| c.is_digit(kind.base()))?;
+    take_digits(buf, cursor, kind.base(), true)
+}
 
-    Ok(())
+/// Consumes the digits of the given base, until a non-digit character is encountered or all the
+/// characters are consumed.
+///
+/// If `separators` is `true`, a `NumericLiteralSeparator` (`_`) can appear between two digits.
+/// Separators are not added to the buffer.
+///
+/// More information:
+///  - [ECMAScript Specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-NumericLiteralSeparator
+fn take_digits<R>(
+    buf: &mut String,
+    cursor: &mut Cursor<R>,
+    base: u32,
+    separators: bool,
+) -> Result<(), Error>
+where
+    R: Read,
+{
+    let mut after_digit = buf.ends_with(|c: char| c.is_digit(base));
+
+    loop {
+        match cursor.peek()? {
+            Some(c) if c.is_digit(base) => {
+                cursor.next_char()?.expect("digit vanished");
+                buf.push(c);
+                after_digit = true;
+            }
+            Some('_') if separators => {
+                let separator_pos = cursor.pos();
+                cursor.next_char()?.expect("_ character vanished");
+
+                if !after_digit || !cursor.next_is_pred(&|c: char| c.is_digit(base))? {
+                    return Err(Error::syntax(
+                        "numeric separators are only allowed between digits",
+                        separator_pos,
+                    ));
+                }
+                after_digit = false;
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// Checks that the digits of a literal with a radix prefix were all consumed.
+///
+/// The prefix must be followed by at least one digit, and a decimal digit that is not valid in
+/// the base can't end the literal: `0b12` is an error instead of `0b1` followed by `2`.
+fn check_radix_digits<R>(
+    digits: &str,
+    cursor: &mut Cursor<R>,
+    prefix: char,
+    base: u32,
+) -> Result<(), Error>
+where
+    R: Read,
+{
+    let name = match base {
+        2 => "binary",
+        8 => "octal",
+        _ => "hexadecimal",
+    };
+
+    match cursor.peek()? {
+        Some(ch) if ch.is_digit(10) => Err(Error::syntax(
+            format!("invalid digit `{}` in {} literal", ch, name),
+            cursor.pos(),
+        )),
+        _ if digits.is_empty() => Err(Error::syntax(
+            format!(
+                "{} literal with no digits after the `0{}` prefix",
+                name, prefix
+            ),
+            cursor.pos(),
+        )),
+        _ => Ok(()),
+    }
 }
 
 /// Utility function for checking the NumericLiteral is not followed by an `IdentifierStart` or `DecimalDigit` character.
@@ -145,6 +223,13 @@ impl<R> Tokenizer<R> for NumberLiteral {
         // Default assume the number is a base 10 integer.
         let mut kind = NumericKind::Integer(10);
 
+        // Whether the literal starts with a `0` that is not a radix prefix. Numeric separators and
+        // the BigInt suffix are not allowed in such literals.
+        let mut leading_zero = self.init == '0';
+
+        // The `x`, `o` or `b` of the radix prefix, if any.
+        let mut prefix = None;
+
         let c = cursor.peek();
 
         if self.init == '0' {
@@ -156,6 +241,8 @@ impl<R> Tokenizer<R> for NumberLiteral {
                         buf.pop();
 
                         // HexIntegerLiteral
+                        leading_zero = false;
+                        prefix = Some(ch);
                         kind = NumericKind::Integer(16);
                     }
                     'o' | 'O' => {
@@ -164,6 +251,8 @@ impl<R> Tokenizer<R> for NumberLiteral {
                         buf.pop();
 
                         // OctalIntegerLiteral
+                        leading_zero = false;
+                        prefix = Some(ch);
                         kind = NumericKind::Integer(8);
                     }
                     'b' | 'B' => {
@@ -172,6 +261,8 @@ impl<R> Tokenizer<R> for NumberLiteral {
                         buf.pop();
 
                         // BinaryIntegerLiteral
+                        leading_zero = false;
+                        prefix = Some(ch);
                         kind = NumericKind::Integer(2);
                     }
                     'n' => {
@@ -226,7 +317,11 @@ impl<R> Tokenizer<R> for NumberLiteral {
         }
 
         // Consume digits until a non-digit character is encountered or all the characters are consumed.
-        cursor.take_while_pred(&mut buf, &|c: char| c.is_digit(kind.base()))?;
+        take_digits(&mut buf, cursor, kind.base(), !leading_zero)?;
+
+        if let Some(prefix) = prefix {
+            check_radix_digits(&buf, cursor, prefix, kind.base())?;
+        }
 
         // The non-digit character could be:
         // 'n' To indicate a BigIntLiteralSuffix.
@@ -237,6 +332,13 @@ impl<R> Tokenizer<R> for NumberLiteral {
                 // DecimalBigIntegerLiteral
                 // Lexing finished.
 
+                if leading_zero {
+                    return Err(Error::syntax(
+                        "BigInt literals must not have a leading zero",
+                        cursor.pos(),
+                    ));
+                }
+
                 // Consume the n
                 cursor.next_char()?.expect("n character vanished");
 
@@ -252,7 +354,7 @@ impl<R> Tokenizer<R> for NumberLiteral {
                     kind = NumericKind::Rational;
 
                     // Consume digits until a non-digit character is encountered or all the characters are consumed.
-                    cursor.take_while_pred(&mut buf, &|c: char| c.is_digit(kind.base()))?;
+                    take_digits(&mut buf, cursor, kind.base(), true)?;
 
                     // The non-digit character at this point must be an 'e' or 'E' to indicate an Exponent Part.
                     // Another '.' or 'n' is not allowed.
@@ -282,6 +384,14 @@ impl<R> Tokenizer<R> for NumberLiteral {
             }
         }
 
+        // The BigInt suffix is only allowed in integer literals.
+        if kind == NumericKind::Rational && cursor.next_is_pred(&|c: char| c == 'n')? {
+            return Err(Error::syntax(
+                "BigInt literals must not have a decimal point or an exponent",
+                cursor.pos(),
+            ));
+        }
+
         check_after_numeric_literal(cursor)?;
 
         let num = match kind {
@@ -40,52 +40,53 @@ impl<R> Tokenizer<R> for RegexLiteral {
         let _timer = BoaProfiler::global().start_event("RegexLiteral", "Lexing");
 
         let mut body = String::new();
+        // A `/` doesn't end the body inside a character class, such as in `/[/]/`.
+        let mut in_class = false;
 
         // Lex RegularExpressionBody.
         loop {
-            match cursor.next_char()? {
-                None => {
-                    // Abrupt end.
+            let c = cursor.next_char()?.ok_or_else(|| {
+                Error::syntax("unterminated regular expression literal", start_pos)
+            })?;
+
+            match c {
+                '/' if !in_class => break, // RegularExpressionBody finished.
+                '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
+                    // Not allowed in Regex literal.
                     return Err(Error::syntax(
-                        "abrupt end on regular expression",
+                        "new lines are not allowed in regular expressions",
                         cursor.pos(),
                     ));
                 }
-                Some(c) => {
-                    match c {
-                        '/' => break, // RegularExpressionBody finished.
-                        '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
+                '\\' => {
+                    // Escape sequence, the escaped character never ends the body or a class.
+                    body.push('\\');
+                    match cursor.next_char()? {
+                        Some('\n') | Some('\r') | Some('\u{2028}') | Some('\u{2029}') => {
                             // Not allowed in Regex literal.
                             return Err(Error::syntax(
                                 "new lines are not allowed in regular expressions",
                                 cursor.pos(),
                             ));
                         }
-                        '\\' => {
-                            // Escape sequence
-                            body.push('\\');
-                            if let Some(sc) = cursor.next_char()? {
-                                match sc {
-                                    '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
-                                        // Not allowed in Regex literal.
-                                        return Err(Error::syntax(
-                                            "new lines are not allowed in regular expressions",
-                                            cursor.pos(),
-                                        ));
-                                    }
-                                    ch => body.push(ch),
-                                }
-                            } else {
-                                // Abrupt end of regex.
-                                return Err(Error::syntax(
-                                    "abrupt end on regular expression",
-                                    cursor.pos(),
-                                ));
-                            }
+                        Some(ch) => body.push(ch),
+                        None => {
+                            return Err(Error::syntax(
+                                "unterminated regular expression literal",
+                                start_pos,
+                            ));
                         }
-                        _ => body.push(c),
                     }
                 }
+                '[' => {
+                    in_class = true;
+                    body.push(c);
+                }
+                ']' => {
+                    in_class = false;
+                    body.push(c);
+                }
+                _ => body.push(c),
             }
         }
 
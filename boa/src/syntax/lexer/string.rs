@@ -1,6 +1,6 @@
 //! This module implements lexing for string literals used in the JavaScript programing language.
 
-use super::{Cursor, Error, Tokenizer};
+use super::{is_line_terminator, Cursor, Error, Tokenizer};
 use crate::{
     profiler::BoaProfiler,
     syntax::{
@@ -8,12 +8,7 @@ use crate::{
         lexer::{Token, TokenKind},
     },
 };
-use std::{
-    char::{decode_utf16, from_u32},
-    convert::TryFrom,
-    io::{self, ErrorKind, Read},
-    str,
-};
+use std::{char::decode_utf16, convert::TryFrom, io::Read, str};
 
 /// String literal lexing.
 ///
@@ -28,18 +23,22 @@ use std::{
 #[derive(Debug, Clone, Copy)]
 pub(super) struct StringLiteral {
     terminator: StringTerminator,
+    strict: bool,
 }
 
 impl StringLiteral {
     /// Creates a new string literal lexer.
-    pub(super) fn new(init: char) -> Self {
+    ///
+    /// `strict` tells whether the literal is lexed in strict mode code, which forbids legacy octal
+    /// escape sequences and unknown escape sequences.
+    pub(super) fn new(init: char, strict: bool) -> Self {
         let terminator = match init {
             '\'' => StringTerminator::SingleQuote,
             '"' => StringTerminator::DoubleQuote,
             _ => unreachable!(),
         };
 
-        Self { terminator }
+        Self { terminator, strict }
     }
 }
 
@@ -50,6 +49,16 @@ enum StringTerminator {
     DoubleQuote,
 }
 
+impl StringTerminator {
+    /// Gets the quote character of the terminator.
+    fn quote(self) -> char {
+        match self {
+            Self::SingleQuote => '\'',
+            Self::DoubleQuote => '"',
+        }
+    }
+}
+
 impl<R> Tokenizer<R> for StringLiteral {
     fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
     where
@@ -57,15 +66,15 @@ impl<R> Tokenizer<R> for StringLiteral {
     {
         let _timer = BoaProfiler::global().start_event("StringLiteral", "Lexing");
 
+        // The opening quote was already consumed, the closing one is part of the recording.
+        cursor.start_recording();
+
         let mut buf = String::new();
         loop {
-            let next_chr_start = cursor.pos();
-            let next_chr = cursor.next_char()?.ok_or_else(|| {
-                Error::from(io::Error::new(
-                    ErrorKind::UnexpectedEof,
-                    "unterminated string literal",
-                ))
-            })?;
+            let chr_pos = cursor.pos();
+            let next_chr = cursor
+                .next_char()?
+                .ok_or_else(|| Error::syntax("unterminated string literal", start_pos))?;
 
             match next_chr {
                 '\'' if self.terminator == StringTerminator::SingleQuote => {
@@ -74,138 +83,457 @@ impl<R> Tokenizer<R> for StringLiteral {
                 '"' if self.terminator == StringTerminator::DoubleQuote => {
                     break;
                 }
-                '\\' => {
-                    let _timer = BoaProfiler::global()
-                        .start_event("StringLiteral - escape sequence", "Lexing");
-
-                    let escape = cursor.next_char()?.ok_or_else(|| {
-                        Error::from(io::Error::new(
-                            ErrorKind::UnexpectedEof,
-                            "unterminated escape sequence in string literal",
-                        ))
-                    })?;
-                    if escape != '\n' {
-                        let escaped_ch = match escape {
-                            'n' => '\n',
-                            'r' => '\r',
-                            't' => '\t',
-                            'b' => '\x08',
-                            'f' => '\x0c',
-                            '0' => '\0',
-                            'x' => {
-                                let mut nums = [0u8; 2];
-                                cursor.fill_bytes(&mut nums)?;
-                                let nums = str::from_utf8(&nums).expect("non-UTF-8 bytes found");
-
-                                let as_num = match u64::from_str_radix(&nums, 16) {
-                                    Ok(v) => v,
-                                    Err(_) => 0,
-                                };
-                                match from_u32(as_num as u32) {
-                                    Some(v) => v,
-                                    None => {
-                                        return Err(Error::syntax(
-                                            format!(
-                                                "{}: {} is not a valid Unicode scalar value",
-                                                cursor.pos(),
-                                                as_num
-                                            ),
-                                            cursor.pos(),
-                                        ))
-                                    }
-                                }
-                            }
-                            'u' => {
-                                // There are 2 types of codepoints. Surragate codepoints and
-                                // unicode codepoints. UTF-16 could be surrogate codepoints,
-                                // "\uXXXX\uXXXX" which make up a single unicode codepoint. We will
-                                //  need to loop to make sure we catch all UTF-16 codepoints
-
-                                // Support \u{X..X} (Unicode Codepoint)
-                                if cursor.next_is('{')? {
-                                    cursor.next_char()?.expect("{ character vanished"); // Consume the '{'.
-
-                                    // The biggest code point is 0x10FFFF
-                                    // TODO: use bytes for a bit better performance (using stack)
-                                    let mut code_point = String::with_capacity(6);
-                                    cursor.take_until('}', &mut code_point)?;
-
-                                    cursor.next_char()?.expect("} character vanished"); // Consume the '}'.
-
-                                    // We know this is a single unicode codepoint, convert to u32
-                                    let as_num =
-                                        u32::from_str_radix(&code_point, 16).map_err(|_| {
-                                            Error::syntax(
-                                                "malformed Unicode character escape sequence",
-                                                cursor.pos(),
-                                            )
-                                        })?;
-                                    if as_num > 0x10_FFFF {
-                                        return Err(Error::syntax("Unicode codepoint must not be greater than 0x10FFFF in escape sequence", cursor.pos()));
-                                    }
-                                    char::try_from(as_num).map_err(|_| {
-                                        Error::syntax(
-                                            "invalid Unicode escape sequence",
-                                            cursor.pos(),
-                                        )
-                                    })?
-                                } else {
-                                    let mut codepoints: Vec<u16> = vec![];
-                                    loop {
-                                        // Collect each character after \u e.g \uD83D will give "D83D"
-                                        let mut code_point = [0u8; 4];
-                                        cursor.fill_bytes(&mut code_point)?;
-
-                                        // Convert to u16
-                                        let as_num = match u16::from_str_radix(
-                                            str::from_utf8(&code_point)
-                                                .expect("the cursor returned invalid UTF-8"),
-                                            16,
-                                        ) {
-                                            Ok(v) => v,
-                                            Err(_) => 0,
-                                        };
-
-                                        codepoints.push(as_num);
-
-                                        // Check for another UTF-16 codepoint
-                                        if cursor.next_is('\\')? && cursor.next_is('u')? {
-                                            continue;
-                                        }
-                                        break;
-                                    }
-
-                                    // codepoints length should either be 1 (unicode codepoint) or
-                                    // 2 (surrogate codepoint). Rust's decode_utf16 will deal with
-                                    // it regardless
-                                    // TODO: do not panic with invalid code points.
-                                    decode_utf16(codepoints.iter().copied())
-                                        .next()
-                                        .expect("Could not get next codepoint")
-                                        .expect("Could not get next codepoint")
-                                }
-                            }
-                            '\'' | '"' | '\\' => escape,
-                            ch => {
-                                let details = format!(
-                                    "invalid escape sequence `{}` at line {}, column {}",
-                                    next_chr_start.line_number(),
-                                    next_chr_start.column_number(),
-                                    ch
-                                );
-                                return Err(Error::syntax(details, cursor.pos()));
-                            }
-                        };
-                        buf.push(escaped_ch);
-                    }
+                '\\' => decode_escape(cursor, self.strict, &mut buf)?,
+                // Unlike the other line terminators, `<LS>` and `<PS>` are allowed in string
+                // literals since ES2019.
+                '\n' | '\r' => {
+                    return Err(Error::syntax(
+                        "line terminators are not allowed in string literals",
+                        chr_pos,
+                    ));
                 }
                 next_ch => buf.push(next_ch),
             }
         }
 
+        let raw = format!("{}{}", self.terminator.quote(), cursor.take_recording());
+
         Ok(Token::new(
-            TokenKind::string_literal(buf),
+            TokenKind::string_literal(buf, raw),
             Span::new(start_pos, cursor.pos()),
         ))
     }
 }
+
+/// Decodes an escape sequence, whose `\` has already been consumed from the cursor, and pushes
+/// its characters to `buf`.
+///
+/// `strict` forbids the legacy octal escape sequences and the unknown escape sequences allowed by
+/// Annex B. A `LineContinuation` doesn't produce any character, so nothing is pushed for it.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-EscapeSequence
+pub(super) fn decode_escape<R>(
+    cursor: &mut Cursor<R>,
+    strict: bool,
+    buf: &mut String,
+) -> Result<(), Error>
+where
+    R: Read,
+{
+    let _timer = BoaProfiler::global().start_event("escape sequence", "Lexing");
+
+    // The backslash is the character right before the escape sequence, on the same line.
+    let backslash_pos = Position::new(cursor.pos().line_number(), cursor.pos().column_number() - 1);
+    let escape = cursor
+        .next_char()?
+        .ok_or_else(|| Error::syntax("unterminated escape sequence", backslash_pos))?;
+    // A `LineContinuation` produces no character. The cursor already consumed the `\n` of a
+    // `\r\n` pair.
+    if is_line_terminator(escape) {
+        return Ok(());
+    }
+
+    let escaped_ch = match escape {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        'b' => '\x08',
+        'v' => '\x0b',
+        'f' => '\x0c',
+        // `\0` is only a NUL character when no decimal digit follows it, `\01` is a legacy octal
+        // escape sequence.
+        '0' if !cursor.next_is_pred(&|c: char| c.is_digit(10))? => '\0',
+        '0'..='7' => {
+            // LegacyOctalEscapeSequence, forbidden in strict mode.
+            if strict {
+                return Err(Error::syntax(
+                    "octal escape sequences are not allowed in strict mode",
+                    cursor.pos(),
+                ));
+            }
+
+            cursor.warn("octal escape sequences are deprecated", backslash_pos);
+            take_legacy_octal_escape(cursor, escape)?
+        }
+        'x' => match decode_hex_escape(cursor) {
+            // In recovery mode, a malformed escape becomes a replacement character.
+            Err(Error::Syntax(_, _)) if cursor.recover() => '\u{FFFD}',
+            decoded => decoded?,
+        },
+        'u' => return push_unicode_escape(cursor, strict, backslash_pos, buf),
+        '\'' | '"' | '\\' => escape,
+        // Annex B: in non-strict code, any other character is an identity
+        // escape, like browsers do.
+        ch if !strict => ch,
+        ch => {
+            let details = format!(
+                "invalid escape sequence `{}` at line {}, column {}",
+                ch,
+                backslash_pos.line_number(),
+                backslash_pos.column_number(),
+            );
+            return Err(Error::syntax(details, cursor.pos()));
+        }
+    };
+
+    buf.push(escaped_ch);
+    Ok(())
+}
+
+/// Decodes the rest of a `\xXX` escape sequence, whose `\x` has already been consumed.
+fn decode_hex_escape<R>(cursor: &mut Cursor<R>) -> Result<char, Error>
+where
+    R: Read,
+{
+    let mut digits = ['\0'; 2];
+    let len = take_escape_chars(cursor, "\\x", &mut digits)?;
+    let digits = &digits[..len];
+
+    // A `HexEscapeSequence` is exactly two hexadecimal digits. Its
+    // code unit is always below 0x100, so it is a Unicode scalar value
+    // on its own and never takes part in a surrogate pair.
+    match hex_value(digits) {
+        Some(value) if len == 2 => Ok(char::from(value as u8)),
+        _ => Err(Error::syntax(
+            format!(
+                "invalid hexadecimal escape sequence `\\x{}`",
+                digits.iter().collect::<String>()
+            ),
+            cursor.pos(),
+        )),
+    }
+}
+
+/// Decodes the rest of a `\u` escape sequence, whose `\u` has already been consumed, and pushes
+/// its character to `buf`.
+///
+/// In recovery mode, a malformed escape becomes a replacement character. When the malformed escape
+/// is a high surrogate whose low surrogate was looked for in an escape sequence that turned out to
+/// be another one, the start of that escape sequence is already consumed, so it is decoded here.
+fn push_unicode_escape<R>(
+    cursor: &mut Cursor<R>,
+    strict: bool,
+    backslash_pos: Position,
+    buf: &mut String,
+) -> Result<(), Error>
+where
+    R: Read,
+{
+    let next = match decode_code_units(cursor, backslash_pos) {
+        Ok(UnicodeEscape::CodePoint(ch)) => {
+            buf.push(ch);
+            return Ok(());
+        }
+        Ok(UnicodeEscape::LoneSurrogate(_, next)) if cursor.recover() => next,
+        Ok(UnicodeEscape::LoneSurrogate(e, _)) => return Err(e),
+        Err(Error::Syntax(_, _)) if cursor.recover() => NextEscape::Nothing,
+        Err(e) => return Err(e),
+    };
+
+    buf.push('\u{FFFD}');
+    match next {
+        NextEscape::Nothing => Ok(()),
+        NextEscape::Escape => decode_escape(cursor, strict, buf),
+        NextEscape::Unicode(backslash_pos) => {
+            push_unicode_escape(cursor, strict, backslash_pos, buf)
+        }
+    }
+}
+
+/// Decodes the rest of a `\uXXXX` or `\u{X..X}` escape sequence, whose `\u` has already been
+/// consumed.
+///
+/// `backslash_pos` is the position of the `\` starting the escape sequence.
+pub(super) fn decode_unicode_escape<R>(
+    cursor: &mut Cursor<R>,
+    backslash_pos: Position,
+) -> Result<char, Error>
+where
+    R: Read,
+{
+    match decode_code_units(cursor, backslash_pos)? {
+        UnicodeEscape::CodePoint(ch) => Ok(ch),
+        UnicodeEscape::LoneSurrogate(e, _) => Err(e),
+    }
+}
+
+/// A decoded `\u` escape sequence.
+#[derive(Debug)]
+enum UnicodeEscape {
+    /// The escape sequence is a whole code point.
+    CodePoint(char),
+    /// The escape sequence is a high surrogate that is not followed by a low surrogate, while
+    /// the start of the escape sequence after it was consumed to look for it.
+    LoneSurrogate(Error, NextEscape),
+}
+
+/// The start of the escape sequence following a lone high surrogate, that was consumed while
+/// looking for its low surrogate.
+#[derive(Debug, Clone, Copy)]
+enum NextEscape {
+    /// Nothing was consumed.
+    Nothing,
+    /// The `\` of an escape sequence other than a `\u` one.
+    Escape,
+    /// The `\u` of a `\u{X..X}` escape sequence, whose `\` is at the given position.
+    Unicode(Position),
+}
+
+/// Decodes the rest of a `\uXXXX` or `\u{X..X}` escape sequence, with the `\uXXXX` escape of the
+/// low surrogate that must follow a high surrogate.
+fn decode_code_units<R>(
+    cursor: &mut Cursor<R>,
+    backslash_pos: Position,
+) -> Result<UnicodeEscape, Error>
+where
+    R: Read,
+{
+    // There are 2 types of codepoints. Surragate codepoints and
+    // unicode codepoints. UTF-16 could be surrogate codepoints,
+    // "\uXXXX\uXXXX" which make up a single unicode codepoint. We will
+    //  need to loop to make sure we catch all UTF-16 codepoints
+
+    // Support \u{X..X} (Unicode Codepoint)
+    if cursor.next_is('{')? {
+        // The biggest code point is 0x10FFFF
+        // TODO: use bytes for a bit better performance (using stack)
+        let mut code_point = String::with_capacity(6);
+        while !cursor.next_is('}')? {
+            match cursor.peek()? {
+                Some(ch) if is_escape_char(ch) => {
+                    let ch = cursor.next_char()?.expect("peeked character vanished");
+                    code_point.push(ch);
+                }
+                // A line terminator, a quote or the end of the file can't be part of the escape,
+                // the `}` is missing.
+                _ => {
+                    return Err(Error::syntax(
+                        format!("unterminated \\u{{ escape, got '{}'", code_point),
+                        backslash_pos,
+                    ));
+                }
+            }
+        }
+
+        if code_point.is_empty() {
+            return Err(Error::syntax(
+                "Unicode escape sequence `\\u{}` has no digits",
+                cursor.pos(),
+            ));
+        }
+
+        if !code_point.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::syntax(
+                "malformed Unicode character escape sequence",
+                cursor.pos(),
+            ));
+        }
+
+        // We know this is a single unicode codepoint, convert to u32.
+        // The digits are valid, so this can only fail on overflow.
+        let as_num = u32::from_str_radix(&code_point, 16).ok();
+        if let Some(Ok(ch)) = as_num.map(char::try_from) {
+            Ok(UnicodeEscape::CodePoint(ch))
+        } else {
+            let details = format!(
+                "invalid Unicode escape sequence `\\u{{{}}}`: {}",
+                code_point,
+                describe_invalid_code_point(as_num)
+            );
+            Err(Error::syntax(details, cursor.pos()))
+        }
+    } else {
+        // Collect each character after \u e.g \uD83D will give "D83D"
+        let mut codepoints: Vec<u16> = Vec::with_capacity(2);
+        codepoints.push(take_code_unit(cursor)?);
+
+        // A high surrogate must be followed by its low surrogate,
+        // which comes in another "\uXXXX" escape. A "\u{X..X}" escape is
+        // a whole code point, so it never completes a surrogate pair.
+        let mut next = NextEscape::Nothing;
+        if (0xD800..=0xDBFF).contains(&codepoints[0]) {
+            let next_backslash_pos = cursor.pos();
+            if cursor.next_is('\\')? {
+                if !cursor.next_is('u')? {
+                    next = NextEscape::Escape;
+                } else if cursor.next_is_pred(&|c: char| c == '{')? {
+                    next = NextEscape::Unicode(next_backslash_pos);
+                } else {
+                    codepoints.push(take_code_unit(cursor)?);
+                }
+            }
+        }
+
+        // codepoints length should either be 1 (unicode codepoint) or
+        // 2 (surrogate codepoint). Anything that does not decode to a
+        // single character is a lone or malformed surrogate.
+        if let Some(Ok(ch)) = decode_utf16(codepoints.iter().copied()).next() {
+            Ok(UnicodeEscape::CodePoint(ch))
+        } else {
+            let sequence: String = codepoints
+                .iter()
+                .map(|unit| format!("\\u{:04X}", unit))
+                .collect();
+            // The first code unit is the one that can't be decoded: either a low surrogate, or
+            // a high surrogate that is not followed by a low one.
+            let reason = if (0xD800..=0xDBFF).contains(&codepoints[0]) {
+                format!(
+                    "the high surrogate U+{:04X} is not followed by a low surrogate",
+                    codepoints[0]
+                )
+            } else {
+                format!(
+                    "the low surrogate U+{:04X} is not preceded by a high surrogate",
+                    codepoints[0]
+                )
+            };
+            let e = Error::syntax(
+                format!(
+                    "invalid surrogate sequence `{}` in Unicode escape sequence: {}",
+                    sequence, reason
+                ),
+                cursor.pos(),
+            );
+            match next {
+                NextEscape::Nothing => Err(e),
+                next => Ok(UnicodeEscape::LoneSurrogate(e, next)),
+            }
+        }
+    }
+}
+
+/// Explains why a code point can't be part of a string, for the errors of Unicode escape
+/// sequences.
+///
+/// `code_point` is `None` if it doesn't even fit in a `u32`.
+fn describe_invalid_code_point(code_point: Option<u32>) -> String {
+    match code_point {
+        Some(code_point @ 0xD800..=0xDBFF) => {
+            format!("U+{:04X} is a high surrogate code point", code_point)
+        }
+        Some(code_point @ 0xDC00..=0xDFFF) => {
+            format!("U+{:04X} is a low surrogate code point", code_point)
+        }
+        Some(code_point) => format!(
+            "0x{:X} is above the greatest code point U+10FFFF",
+            code_point
+        ),
+        None => "the code point is above the greatest code point U+10FFFF".to_owned(),
+    }
+}
+
+/// Reads the characters following an escape, such as the digits of a `\xXX` escape, into `chars`,
+/// and returns how many were read.
+///
+/// Fewer characters than `chars` can hold are only read when recovering. `escape` is the start of
+/// the escape sequence, to name it if the end of the file is found.
+fn take_escape_chars<R>(
+    cursor: &mut Cursor<R>,
+    escape: &str,
+    chars: &mut [char],
+) -> Result<usize, Error>
+where
+    R: Read,
+{
+    for len in 0..chars.len() {
+        // When recovering, a malformed escape can't swallow the closing quote or the next
+        // escape sequence.
+        if cursor.recover() && !cursor.next_is_pred(&is_escape_char)? {
+            return Ok(len);
+        }
+
+        match cursor.next_char()? {
+            Some(ch) => chars[len] = ch,
+            None => {
+                return Err(Error::syntax(
+                    format!(
+                        "unterminated {} escape, got '{}'",
+                        escape,
+                        chars[..len].iter().collect::<String>()
+                    ),
+                    cursor.pos(),
+                ));
+            }
+        }
+    }
+
+    Ok(chars.len())
+}
+
+/// Checks if a malformed escape sequence can go on with the given character, when recovering.
+fn is_escape_char(ch: char) -> bool {
+    !matches!(ch, '\'' | '"' | '\\') && !is_line_terminator(ch)
+}
+
+/// Gets the value of an ASCII hexadecimal digit.
+fn hex_digit_value(ch: char) -> Option<u32> {
+    match ch {
+        '0'..='9' => Some(ch as u32 - '0' as u32),
+        'a'..='f' => Some(ch as u32 - 'a' as u32 + 10),
+        'A'..='F' => Some(ch as u32 - 'A' as u32 + 10),
+        _ => None,
+    }
+}
+
+/// Gets the value of a sequence of hexadecimal digits, or `None` if one of them is not a
+/// hexadecimal digit.
+fn hex_value(digits: &[char]) -> Option<u32> {
+    digits.iter().try_fold(0, |value, &digit| {
+        Some(value << 4 | hex_digit_value(digit)?)
+    })
+}
+
+/// Reads the four hexadecimal digits of a `\uXXXX` escape as a UTF-16 code unit.
+fn take_code_unit<R>(cursor: &mut Cursor<R>) -> Result<u16, Error>
+where
+    R: Read,
+{
+    let mut digits = ['\0'; 4];
+    let len = take_escape_chars(cursor, "\\u", &mut digits)?;
+    let digits = &digits[..len];
+
+    match hex_value(digits) {
+        Some(unit) if len == 4 => Ok(unit as u16),
+        _ => Err(Error::syntax(
+            format!(
+                "invalid Unicode escape sequence `\\u{}`",
+                digits.iter().collect::<String>()
+            ),
+            cursor.pos(),
+        )),
+    }
+}
+
+/// Reads the rest of a legacy octal escape sequence (`\7`, `\12`, `\101`), whose first digit has
+/// already been consumed.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-annexB-LegacyOctalEscapeSequence
+fn take_legacy_octal_escape<R>(cursor: &mut Cursor<R>, init: char) -> Result<char, Error>
+where
+    R: Read,
+{
+    let mut as_num = init
+        .to_digit(8)
+        .expect("the first digit must be an octal digit");
+
+    // A leading `0` to `3` can be followed by two more digits, `4` to `7` only by one, so that
+    // the value never goes above 0o377.
+    let max_len = if as_num <= 3 { 3 } else { 2 };
+    for _ in 1..max_len {
+        match cursor.peek()?.and_then(|c| c.to_digit(8)) {
+            Some(digit) => {
+                cursor.next_char()?.expect("octal digit vanished");
+                as_num = as_num * 8 + digit;
+            }
+            None => break,
+        }
+    }
+
+    Ok(char::from(as_num as u8))
+}
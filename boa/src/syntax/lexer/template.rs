@@ -1,6 +1,6 @@
 //! This module implements lexing for template literals used in the JavaScript programing language.
 
-use super::{Cursor, Error, Tokenizer};
+use super::{string::decode_escape, ColumnUnit, Cursor, Error, Tokenizer};
 use crate::{
     profiler::BoaProfiler,
     syntax::{
@@ -8,11 +8,17 @@ use crate::{
         lexer::{Token, TokenKind},
     },
 };
-use std::io::{self, ErrorKind, Read};
+use std::io::Read;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Template literal lexing.
 ///
-/// Expects: Initial ` to already be consumed by cursor.
+/// Lexes a part of a template literal, up to the closing `` ` `` or to the `${` of the next
+/// substitution.
+///
+/// Expects: Initial `` ` `` (or the `}` ending a substitution) to already be consumed by cursor.
 ///
 /// More information:
 ///  - [ECMAScript reference][spec]
@@ -21,7 +27,20 @@ use std::io::{self, ErrorKind, Read};
 /// [spec]: https://tc39.es/ecma262/#sec-template-literals
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals
 #[derive(Debug, Clone, Copy)]
-pub(super) struct TemplateLiteral;
+pub(super) struct TemplateLiteral {
+    after_substitution: bool,
+}
+
+impl TemplateLiteral {
+    /// Creates a new template literal lexer.
+    ///
+    /// `after_substitution` tells whether the part comes after a substitution, which makes it a
+    /// `TemplateMiddle` or a `TemplateTail` instead of a `TemplateHead` or a
+    /// `NoSubstitutionTemplate`.
+    pub(super) fn new(after_substitution: bool) -> Self {
+        Self { after_substitution }
+    }
+}
 
 impl<R> Tokenizer<R> for TemplateLiteral {
     fn lex(&mut self, cursor: &mut Cursor<R>, start_pos: Position) -> Result<Token, Error>
@@ -30,23 +49,135 @@ impl<R> Tokenizer<R> for TemplateLiteral {
     {
         let _timer = BoaProfiler::global().start_event("TemplateLiteral", "Lexing");
 
-        let mut buf = String::new();
-        loop {
+        // The raw string is the source text, where the `\r\n` and `\r` line terminators are
+        // normalized to `\n`. Line continuations are kept, only the cooked string drops them.
+        let mut raw = String::new();
+        let substitution = loop {
             match cursor.next_char()? {
                 None => {
-                    return Err(Error::from(io::Error::new(
-                        ErrorKind::UnexpectedEof,
-                        "Unterminated template literal",
-                    )));
+                    return Err(Error::syntax("unterminated template literal", start_pos));
+                }
+                Some('`') => break false,
+                Some('$') if cursor.peek()? == Some('{') => {
+                    cursor.next_char()?.expect("{ token vanished");
+                    break true;
                 }
-                Some('`') => break,                 // Template literal finished.
-                Some(next_ch) => buf.push(next_ch), // TODO when there is an expression inside the literal
+                Some('\\') => {
+                    raw.push('\\');
+
+                    let escape_pos = cursor.pos();
+                    let escape = cursor
+                        .next_char()?
+                        .ok_or_else(|| Error::syntax("unterminated template literal", start_pos))?;
+
+                    // Only `\0` is allowed, the legacy octal escape sequences and `\8` and `\9`
+                    // are not.
+                    if escape.is_digit(10)
+                        && (escape != '0' || cursor.next_is_pred(&|c: char| c.is_digit(10))?)
+                    {
+                        return Err(Error::syntax(
+                            "octal escape sequences are not allowed in template literals",
+                            escape_pos,
+                        ));
+                    }
+
+                    raw.push(if escape == '\r' { '\n' } else { escape });
+                }
+                Some('\r') => raw.push('\n'),
+                Some(ch) => raw.push(ch),
             }
+        };
+
+        // The content starts right after the `` ` `` or the `}`.
+        let content_pos = Position::new(start_pos.line_number(), start_pos.column_number() + 1);
+        let cooked = cook(&raw, content_pos, cursor.column_unit())?;
+
+        let kind = match (self.after_substitution, substitution) {
+            (false, false) => TokenKind::template_literal(raw, cooked),
+            (false, true) => TokenKind::template_head(raw, cooked),
+            (true, true) => TokenKind::template_middle(raw, cooked),
+            (true, false) => TokenKind::template_tail(raw, cooked),
+        };
+
+        Ok(Token::new(kind, Span::new(start_pos, cursor.pos())))
+    }
+}
+
+/// Computes the cooked string of a part of a template literal, by decoding the escape sequences
+/// in its raw string.
+///
+/// `start_pos` is the position of the raw string in the source, used for the errors, and
+/// `column_unit` how its columns are counted.
+fn cook(raw: &str, start_pos: Position, column_unit: ColumnUnit) -> Result<String, Error> {
+    let mut cursor = Cursor::new(raw.as_bytes());
+    cursor.set_column_unit(column_unit);
+    let mut cooked = String::with_capacity(raw.len());
+
+    while let Some(ch) = cursor.next_char()? {
+        if ch == '\\' {
+            // Octal escapes were already rejected, so what is left is what non-strict string
+            // literals allow.
+            decode_escape(&mut cursor, false, &mut cooked).map_err(|e| match e {
+                Error::Syntax(msg, pos) => {
+                    let pos = if pos.line_number() == 1 {
+                        Position::new(
+                            start_pos.line_number(),
+                            start_pos.column_number() + pos.column_number() - 1,
+                        )
+                    } else {
+                        Position::new(
+                            start_pos.line_number() + pos.line_number() - 1,
+                            pos.column_number(),
+                        )
+                    };
+                    Error::Syntax(msg, pos)
+                }
+                e => e,
+            })?;
+        } else {
+            cooked.push(ch);
+        }
+    }
+
+    Ok(cooked)
+}
+
+/// The raw and cooked strings of a part of a template literal.
+///
+/// The raw string is the source text of the part, while the cooked string is its value once the
+/// escape sequences are decoded.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-static-semantics-tv-and-trv
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct TemplateString {
+    raw: Box<str>,
+    cooked: Box<str>,
+}
+
+impl TemplateString {
+    /// Creates a new template string from its raw and cooked strings.
+    pub fn new<R, C>(raw: R, cooked: C) -> Self
+    where
+        R: Into<Box<str>>,
+        C: Into<Box<str>>,
+    {
+        Self {
+            raw: raw.into(),
+            cooked: cooked.into(),
         }
+    }
+
+    /// Gets the raw string, as written in the source.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
 
-        Ok(Token::new(
-            TokenKind::template_literal(buf),
-            Span::new(start_pos, cursor.pos()),
-        ))
+    /// Gets the cooked string, with the escape sequences decoded.
+    pub fn cooked(&self) -> &str {
+        &self.cooked
     }
 }
@@ -40,6 +40,108 @@ fn check_single_line_comment() {
     expect_tokens(&mut lexer, &expected);
 }
 
+#[test]
+fn check_single_line_comment_line_separators() {
+    for terminator in &['\r', '\u{2028}', '\u{2029}'] {
+        let src = format!("var //This is a comment{}true", terminator);
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        let expected = [
+            TokenKind::Keyword(Keyword::Var),
+            TokenKind::LineTerminator,
+            TokenKind::BooleanLiteral(true),
+        ];
+
+        expect_tokens(&mut lexer, &expected);
+    }
+}
+
+#[test]
+fn check_multi_line_comment_line_separator() {
+    let mut lexer = Lexer::new("var /* await \u{2028} break */ x".as_bytes());
+
+    let expected = [
+        TokenKind::Keyword(Keyword::Var),
+        TokenKind::LineTerminator,
+        TokenKind::identifier("x"),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_hashbang_comment() {
+    let mut lexer = Lexer::new(&b"#!/usr/bin/env node\nvar x;"[..]);
+
+    let expected = [
+        TokenKind::LineTerminator,
+        TokenKind::Keyword(Keyword::Var),
+        TokenKind::identifier("x"),
+        TokenKind::Punctuator(Punctuator::Semicolon),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn byte_order_mark() {
+    // A leading byte order mark is skipped, so the source starts at its first character.
+    let mut lexer = Lexer::new("\u{FEFF}var x;".as_bytes());
+    let var = lexer.next().unwrap().unwrap();
+    assert_eq!(var.kind(), &TokenKind::Keyword(Keyword::Var));
+    assert_eq!(var.span(), span((1, 1), (1, 4)));
+
+    let mut lexer = Lexer::new("\u{FEFF}#!/usr/bin/env node\nvar x;".as_bytes());
+    let expected = [
+        TokenKind::LineTerminator,
+        TokenKind::Keyword(Keyword::Var),
+        TokenKind::identifier("x"),
+        TokenKind::Punctuator(Punctuator::Semicolon),
+    ];
+    expect_tokens(&mut lexer, &expected);
+
+    // Anywhere else, it's a whitespace.
+    let mut lexer = Lexer::new("var\u{FEFF}x\u{FEFF};".as_bytes());
+    let expected = [
+        TokenKind::Keyword(Keyword::Var),
+        TokenKind::identifier("x"),
+        TokenKind::Punctuator(Punctuator::Semicolon),
+    ];
+    expect_tokens(&mut lexer, &expected);
+
+    // It's kept in string and template literals, even at their start.
+    let mut lexer = Lexer::new("'\u{FEFF}a' `\u{FEFF}b`".as_bytes());
+    let expected = [
+        TokenKind::string_literal("\u{FEFF}a", "'\u{FEFF}a'"),
+        TokenKind::template_literal("\u{FEFF}b", "\u{FEFF}b"),
+    ];
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_hashbang_comment_not_at_start() {
+    for src in &["var x;\n#!/usr/bin/env node", " #!/usr/bin/env node"] {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        let err = loop {
+            match lexer.next() {
+                Ok(Some(_)) => {}
+                Ok(None) => panic!(
+                    "hashbang comment not at the start not rejected for {:?}",
+                    src
+                ),
+                Err(err) => break err,
+            }
+        };
+
+        if let Error::Syntax(msg, _) = err {
+            assert!(msg.starts_with("unexpected '#'"), "{}", msg);
+        } else {
+            panic!("invalid error type");
+        }
+    }
+}
+
 #[test]
 fn check_multi_line_comment() {
     let s = "var /* await \n break \n*/ x";
@@ -60,13 +162,45 @@ fn check_string() {
     let mut lexer = Lexer::new(s.as_bytes());
 
     let expected = [
-        TokenKind::string_literal("aaa"),
-        TokenKind::string_literal("bbb"),
+        TokenKind::string_literal("aaa", "'aaa'"),
+        TokenKind::string_literal("bbb", "\"bbb\""),
     ];
 
     expect_tokens(&mut lexer, &expected);
 }
 
+#[test]
+fn empty_string_literals() {
+    let s = "\"\" '' \"\" + ''";
+    let mut lexer = Lexer::new(s.as_bytes());
+
+    let expected = [
+        (TokenKind::string_literal("", "\"\""), span((1, 1), (1, 3))),
+        (TokenKind::string_literal("", "''"), span((1, 4), (1, 6))),
+        (TokenKind::string_literal("", "\"\""), span((1, 7), (1, 9))),
+        (Punctuator::Add.into(), span((1, 10), (1, 11))),
+        (TokenKind::string_literal("", "''"), span((1, 12), (1, 14))),
+    ];
+
+    for (kind, span) in expected.iter() {
+        let token = lexer.next().unwrap().unwrap();
+        assert_eq!(token.kind(), kind);
+        assert_eq!(token.span(), *span);
+    }
+    assert!(lexer.next().unwrap().is_none());
+
+    // A lone quote is not an empty string.
+    for src in ["\"", "'"].iter() {
+        match Lexer::new(src.as_bytes()).next() {
+            Err(Error::Syntax(msg, pos)) => {
+                assert_eq!(msg.as_ref(), "unterminated string literal");
+                assert_eq!(pos, Position::new(1, 1));
+            }
+            res => panic!("unterminated string not rejected in {}: {:?}", src, res),
+        }
+    }
+}
+
 #[test]
 fn check_template_literal_simple() {
     let s = "`I'm a template literal`";
@@ -74,10 +208,154 @@ fn check_template_literal_simple() {
 
     assert_eq!(
         lexer.next().unwrap().unwrap().kind(),
-        &TokenKind::template_literal("I'm a template literal")
+        &TokenKind::template_literal("I'm a template literal", "I'm a template literal")
+    );
+}
+
+#[test]
+fn check_template_literal_no_substitution() {
+    let mut lexer = Lexer::new(&b"`abc`"[..]);
+
+    let expected = [TokenKind::template_literal("abc", "abc")];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_literal_substitution() {
+    let mut lexer = Lexer::new(&b"`a${1+1}b`"[..]);
+
+    let expected = [
+        TokenKind::template_head("a", "a"),
+        TokenKind::numeric_literal(1),
+        TokenKind::Punctuator(Punctuator::Add),
+        TokenKind::numeric_literal(1),
+        TokenKind::template_tail("b", "b"),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_literal_substitution_spans() {
+    let mut lexer = Lexer::new(&b"`a${x}bc${y}d`;"[..]);
+
+    let spans: Vec<_> = (0..6)
+        .map(|_| lexer.next().unwrap().unwrap().span())
+        .collect();
+
+    assert_eq!(
+        spans,
+        [
+            span((1, 1), (1, 5)),
+            span((1, 5), (1, 6)),
+            span((1, 6), (1, 11)),
+            span((1, 11), (1, 12)),
+            span((1, 12), (1, 15)),
+            span((1, 15), (1, 16)),
+        ]
     );
 }
 
+#[test]
+fn check_template_literal_escape_sequences() {
+    let mut lexer = Lexer::new(&br#"`a\nb\x41\u{42}\``"#[..]);
+
+    let expected = [TokenKind::template_literal(
+        r#"a\nb\x41\u{42}\`"#,
+        "a\nbAB`",
+    )];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_literal_line_terminators() {
+    let mut lexer = Lexer::new(&b"`a\r\nb\rc`"[..]);
+
+    let expected = [TokenKind::template_literal("a\nb\nc", "a\nb\nc")];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_literal_line_continuations() {
+    let mut lexer = Lexer::new("`a\\\nb\\\r\nc\\\u{2028}d${x}e\\\u{2029}f`".as_bytes());
+
+    let expected = [
+        TokenKind::template_head("a\\\nb\\\nc\\\u{2028}d", "abcd"),
+        TokenKind::identifier("x"),
+        TokenKind::template_tail("e\\\u{2029}f", "ef"),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_literal_nested() {
+    let mut lexer = Lexer::new(&b"`a${ {b: `c${d}e`}.b }f${g}h`"[..]);
+
+    let expected = [
+        TokenKind::template_head("a", "a"),
+        TokenKind::Punctuator(Punctuator::OpenBlock),
+        TokenKind::identifier("b"),
+        TokenKind::Punctuator(Punctuator::Colon),
+        TokenKind::template_head("c", "c"),
+        TokenKind::identifier("d"),
+        TokenKind::template_tail("e", "e"),
+        TokenKind::Punctuator(Punctuator::CloseBlock),
+        TokenKind::Punctuator(Punctuator::Dot),
+        TokenKind::identifier("b"),
+        TokenKind::template_middle("f", "f"),
+        TokenKind::identifier("g"),
+        TokenKind::template_tail("h", "h"),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_literal_dollar_without_brace() {
+    let mut lexer = Lexer::new(&b"`$a$`"[..]);
+
+    let expected = [TokenKind::template_literal("$a$", "$a$")];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn check_template_literal_octal_escape() {
+    let mut lexer = Lexer::new(&br#"`a\01`"#[..]);
+
+    if let Error::Syntax(msg, pos) = lexer
+        .next()
+        .expect_err("octal escape sequence in template not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "octal escape sequences are not allowed in template literals"
+        );
+        assert_eq!(pos, Position::new(1, 4));
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn check_template_literal_invalid_escape_position() {
+    let mut lexer = Lexer::new(&b"`a\n\\xZZ`"[..]);
+
+    if let Error::Syntax(msg, pos) = lexer
+        .next()
+        .expect_err("invalid escape sequence in template not rejected as expected")
+    {
+        assert_eq!(msg.as_ref(), "invalid hexadecimal escape sequence `\\xZZ`");
+        assert_eq!(pos, Position::new(2, 5));
+    } else {
+        panic!("invalid error type");
+    }
+}
+
 #[test]
 fn check_template_literal_unterminated() {
     let s = "`I'm a template";
@@ -209,7 +487,7 @@ fn check_variable_definition_tokens() {
         TokenKind::Keyword(Keyword::Let),
         TokenKind::identifier("a"),
         TokenKind::Punctuator(Punctuator::Assign),
-        TokenKind::string_literal("hello"),
+        TokenKind::string_literal("hello", "'hello'"),
         TokenKind::Punctuator(Punctuator::Semicolon),
     ];
 
@@ -258,7 +536,7 @@ fn check_positions() {
 
 #[test]
 fn check_positions_codepoint() {
-    let s = r#"console.log("hello world\u{{2764}}"); // Test"#;
+    let s = r#"console.log("hello world\u{2764}"); // Test"#;
     // --------123456789
     let mut lexer = Lexer::new(s.as_bytes());
 
@@ -280,20 +558,114 @@ fn check_positions_codepoint() {
     // String token starts on column 13
     assert_eq!(
         lexer.next().unwrap().unwrap().span(),
-        span((1, 13), (1, 34))
+        span((1, 13), (1, 32))
     );
 
-    // Close parenthesis token starts on column 34
+    // Close parenthesis token starts on column 32
     assert_eq!(
         lexer.next().unwrap().unwrap().span(),
-        span((1, 34), (1, 35))
+        span((1, 32), (1, 33))
     );
 
-    // Semi Colon token starts on column 35
+    // Semi Colon token starts on column 33
     assert_eq!(
         lexer.next().unwrap().unwrap().span(),
-        span((1, 35), (1, 36))
+        span((1, 33), (1, 34))
+    );
+}
+
+#[test]
+fn check_positions_column_units() {
+    // The emoji is one code point, two UTF-16 code units and four UTF-8 bytes.
+    let s = "\"\u{1F600}\" x";
+    let units = [
+        (ColumnUnit::CodePoint, 4),
+        (ColumnUnit::Utf16, 5),
+        (ColumnUnit::Byte, 7),
+    ];
+
+    for &(unit, string_end) in units.iter() {
+        let mut lexer = Lexer::new(s.as_bytes());
+        lexer.set_column_unit(unit);
+
+        assert_eq!(
+            lexer.next().unwrap().unwrap().span(),
+            span((1, 1), (1, string_end)),
+            "{:?}",
+            unit
+        );
+        assert_eq!(
+            lexer.next().unwrap().unwrap().span(),
+            span((1, string_end + 1), (1, string_end + 2)),
+            "{:?}",
+            unit
+        );
+    }
+}
+
+#[test]
+fn lexer_builder_options() {
+    let lex = |builder: LexerBuilder<&[u8]>| {
+        let mut lexer = builder.build();
+        lexer.next().map(|token| token.unwrap().kind().clone())
+    };
+
+    assert_eq!(
+        lex(LexerBuilder::new(&b"010"[..])).unwrap(),
+        TokenKind::numeric_literal(8)
     );
+    assert!(matches!(
+        lex(LexerBuilder::new(&b"010"[..]).strict(true)),
+        Err(Error::Syntax(..))
+    ));
+
+    let src = r#""\x4G""#;
+    assert!(lex(LexerBuilder::new(src.as_bytes())).is_err());
+    assert_eq!(
+        lex(LexerBuilder::new(src.as_bytes()).recover(true)).unwrap(),
+        TokenKind::string_literal("\u{FFFD}", src)
+    );
+
+    match lex(LexerBuilder::new(&b"0b2"[..]).source_name("x.js")) {
+        Err(e @ Error::Named { .. }) => {
+            assert_eq!(
+                e.to_string(),
+                "Syntax Error: x.js:1:3: invalid digit `2` in binary literal"
+            )
+        }
+        res => panic!("invalid literal not rejected: {:?}", res),
+    }
+
+    let mut lexer = LexerBuilder::new("'\u{1F600}' x".as_bytes())
+        .column_unit(ColumnUnit::Utf16)
+        .build();
+    lexer.next().unwrap().unwrap();
+    assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 6), (1, 7)));
+}
+
+#[test]
+fn check_error_position_column_units() {
+    // The error is reported after the escape sequence.
+    let s = "`\u{1F600}\\xZZ`";
+    let units = [
+        (ColumnUnit::CodePoint, 7),
+        (ColumnUnit::Utf16, 8),
+        (ColumnUnit::Byte, 10),
+    ];
+
+    for &(unit, column) in units.iter() {
+        let mut lexer = Lexer::new(s.as_bytes());
+        lexer.set_column_unit(unit);
+
+        if let Error::Syntax(_, pos) = lexer
+            .next()
+            .expect_err("invalid escape sequence in template not rejected as expected")
+        {
+            assert_eq!(pos, Position::new(1, column), "{:?}", unit);
+        } else {
+            panic!("invalid error type");
+        }
+    }
 }
 
 #[test]
@@ -475,6 +847,48 @@ fn regex_literal_flags() {
     expect_tokens(&mut lexer, &expected);
 }
 
+#[test]
+fn regex_literal_escapes_and_classes() {
+    let cases = [
+        (&br"/a/"[..], "a", RegExpFlags::default()),
+        (br"/a\/b/g", r"a\/b", RegExpFlags::GLOBAL),
+        (br"/[/]/", "[/]", RegExpFlags::default()),
+        (br"/[\]/]/y", r"[\]/]", RegExpFlags::STICKY),
+    ];
+
+    for &(src, body, flags) in cases.iter() {
+        let mut lexer = Lexer::new(src);
+        expect_tokens(
+            &mut lexer,
+            &[TokenKind::regular_expression_literal(body, flags)],
+        );
+    }
+}
+
+#[test]
+fn regex_literal_errors() {
+    let cases = [
+        (&b"/abc"[..], "unterminated regular expression literal"),
+        (b"/[/", "unterminated regular expression literal"),
+        (b"/abc\\", "unterminated regular expression literal"),
+        (b"/abc/gg", "invalid regular expression flag g"),
+        (b"/abc/x", "invalid regular expression flag x"),
+    ];
+
+    for &(src, expected) in cases.iter() {
+        let mut lexer = Lexer::new(src);
+
+        if let Error::Syntax(msg, _) = lexer
+            .next()
+            .expect_err("invalid regular expression not rejected as expected")
+        {
+            assert_eq!(msg.as_ref(), expected);
+        } else {
+            panic!("invalid error type");
+        }
+    }
+}
+
 #[test]
 fn addition_no_spaces() {
     let mut lexer = Lexer::new(&b"1+1"[..]);
@@ -626,6 +1040,147 @@ fn illegal_following_numeric_literal() {
     }
 }
 
+#[test]
+fn numeric_separators() {
+    let mut lexer = Lexer::new(&b"1_000 0b1010_0101 0xFF_FF 1_0.2_5e1_0 1_0n"[..]);
+
+    let expected = [
+        TokenKind::numeric_literal(1000),
+        TokenKind::numeric_literal(0b1010_0101),
+        TokenKind::numeric_literal(0xFFFF),
+        TokenKind::numeric_literal(10.25e10),
+        TokenKind::NumericLiteral(Numeric::BigInt(10.into())),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn invalid_numeric_separators() {
+    for (src, column) in &[
+        ("1__0", 2),
+        ("1_", 2),
+        ("0x_1", 3),
+        ("1_.5", 2),
+        ("1._5", 3),
+        ("1e_1", 4),
+        ("0_1", 2),
+        ("01_2", 3),
+    ] {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        if let Error::Syntax(_, pos) = lexer
+            .next()
+            .expect_err("invalid numeric separator not rejected as expected")
+        {
+            assert_eq!(pos, Position::new(1, *column), "wrong position for {}", src);
+        } else {
+            panic!("invalid error type");
+        }
+    }
+}
+
+#[test]
+fn radix_prefixed_literals() {
+    let mut lexer = Lexer::new(&b"0b1010 0o17 0O777 0B11 0XfF"[..]);
+
+    let expected = [
+        TokenKind::numeric_literal(0b1010),
+        TokenKind::numeric_literal(0o17),
+        TokenKind::numeric_literal(0o777),
+        TokenKind::numeric_literal(0b11),
+        TokenKind::numeric_literal(0xff),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn invalid_radix_prefixed_literals() {
+    for &(src, expected, column) in &[
+        ("0b12", "invalid digit `2` in binary literal", 4),
+        ("0o8", "invalid digit `8` in octal literal", 3),
+        (
+            "0x",
+            "hexadecimal literal with no digits after the `0x` prefix",
+            3,
+        ),
+        (
+            "0B;",
+            "binary literal with no digits after the `0B` prefix",
+            3,
+        ),
+        (
+            "0on",
+            "octal literal with no digits after the `0o` prefix",
+            3,
+        ),
+    ] {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        if let Error::Syntax(msg, pos) = lexer
+            .next()
+            .expect_err("invalid literal not rejected as expected")
+        {
+            assert_eq!(msg.as_ref(), expected, "{}", src);
+            assert_eq!(pos, Position::new(1, column), "wrong position for {}", src);
+        } else {
+            panic!("invalid error type for {}", src);
+        }
+    }
+}
+
+#[test]
+fn leading_underscore_is_identifier() {
+    let mut lexer = Lexer::new(&b"_1"[..]);
+
+    let expected = [TokenKind::identifier("_1")];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn bigint_literals() {
+    let mut lexer = Lexer::new(&b"123n 0xffn 0o17n 0b101n 0n"[..]);
+
+    let expected = [
+        TokenKind::NumericLiteral(Numeric::BigInt(123.into())),
+        TokenKind::NumericLiteral(Numeric::BigInt(255.into())),
+        TokenKind::NumericLiteral(Numeric::BigInt(15.into())),
+        TokenKind::NumericLiteral(Numeric::BigInt(5.into())),
+        TokenKind::NumericLiteral(Numeric::BigInt(0.into())),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn invalid_bigint_literals() {
+    for (src, msg) in &[
+        (
+            "1.5n",
+            "BigInt literals must not have a decimal point or an exponent",
+        ),
+        (
+            "1e3n",
+            "BigInt literals must not have a decimal point or an exponent",
+        ),
+        ("07n", "BigInt literals must not have a leading zero"),
+        ("09n", "BigInt literals must not have a leading zero"),
+    ] {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        if let Error::Syntax(err, _) = lexer
+            .next()
+            .expect_err("invalid BigInt literal not rejected as expected")
+        {
+            assert_eq!(err.as_ref(), *msg, "wrong error for {}", src);
+        } else {
+            panic!("invalid error type");
+        }
+    }
+}
+
 #[test]
 fn codepoint_with_no_braces() {
     let mut lexer = Lexer::new(&br#""test\uD38Dtest""#[..]);
@@ -651,13 +1206,711 @@ fn non_english_str() {
     let mut lexer = Lexer::new(str.as_bytes());
 
     let expected = [
-        TokenKind::StringLiteral("中文".into()),
+        TokenKind::StringLiteral("中文".into(), "'中文'".into()),
         TokenKind::Punctuator(Punctuator::Semicolon),
     ];
 
     expect_tokens(&mut lexer, &expected);
 }
 
+#[test]
+fn string_literal_raw_text() {
+    let mut lexer = Lexer::new(&br#""a\n" 'b\'c'"#[..]);
+
+    let expected = [
+        TokenKind::string_literal("a\n", r#""a\n""#),
+        TokenKind::string_literal("b'c", r#"'b\'c'"#),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+/// Decodes the escape sequence in `src`, which must start with the backslash.
+fn decode(src: &str, strict: bool) -> Result<Option<char>, Error> {
+    let mut cursor = Cursor::new(src.as_bytes());
+    assert_eq!(cursor.next_char().unwrap(), Some('\\'));
+
+    let mut decoded = String::new();
+    string::decode_escape(&mut cursor, strict, &mut decoded)?;
+
+    let mut chars = decoded.chars();
+    let ch = chars.next();
+    assert_eq!(chars.next(), None, "{:?} decoded to {:?}", src, decoded);
+    Ok(ch)
+}
+
+#[test]
+fn decode_escape_single_character() {
+    let escapes = [
+        (r"\n", '\n'),
+        (r"\r", '\r'),
+        (r"\t", '\t'),
+        (r"\b", '\x08'),
+        (r"\v", '\x0b'),
+        (r"\f", '\x0c'),
+        (r"\0", '\0'),
+        (r"\'", '\''),
+        (r#"\""#, '"'),
+        (r"\\", '\\'),
+    ];
+
+    for &(src, expected) in escapes.iter() {
+        assert_eq!(decode(src, true).unwrap(), Some(expected), "{}", src);
+    }
+}
+
+#[test]
+fn decode_escape_hexadecimal_and_unicode() {
+    let escapes = [
+        (r"\x41", 'A'),
+        (r"\u0041", 'A'),
+        (r"\u{41}", 'A'),
+        (r"\u{1F600}", '😀'),
+        (r"\uD83D\uDE00", '😀'),
+    ];
+
+    for &(src, expected) in escapes.iter() {
+        assert_eq!(decode(src, true).unwrap(), Some(expected), "{}", src);
+    }
+}
+
+#[test]
+fn decode_escape_line_continuation() {
+    for &src in ["\\\n", "\\\r\n", "\\\r", "\\\u{2028}", "\\\u{2029}"].iter() {
+        assert_eq!(decode(src, true).unwrap(), None, "{:?}", src);
+    }
+}
+
+#[test]
+fn decode_escape_legacy_octal() {
+    assert_eq!(decode(r"\101", false).unwrap(), Some('A'));
+    assert_eq!(decode(r"\08", false).unwrap(), Some('\0'));
+    assert!(matches!(decode(r"\101", true), Err(Error::Syntax(..))));
+}
+
+#[test]
+fn decode_escape_identity() {
+    assert_eq!(decode(r"\q", false).unwrap(), Some('q'));
+    assert!(matches!(decode(r"\q", true), Err(Error::Syntax(..))));
+}
+
+#[test]
+fn decode_escape_unterminated() {
+    assert!(matches!(decode("\\", false), Err(Error::Syntax(..))));
+}
+
+#[test]
+fn unknown_escape_sequence_non_strict() {
+    let mut lexer = Lexer::new(&br#""\q""#[..]);
+
+    let expected = [TokenKind::string_literal("q", r#""\q""#)];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn unterminated_escape_messages() {
+    let cases = [
+        (r#""\x4"#, "unterminated \\x escape, got '4'"),
+        (r#""\u00"#, "unterminated \\u escape, got '00'"),
+        (r#""\u{12"#, "unterminated \\u{ escape, got '12'"),
+        (r#""abc\"#, "unterminated escape sequence"),
+        (r#""\x4""#, "invalid hexadecimal escape sequence `\\x4\"`"),
+    ];
+
+    for &(src, expected) in cases.iter() {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        if let Error::Syntax(msg, _) = lexer
+            .next()
+            .expect_err("unterminated escape sequence not rejected as expected")
+        {
+            assert_eq!(msg.as_ref(), expected, "{}", src);
+        } else {
+            panic!("invalid error type for {}", src);
+        }
+    }
+}
+
+#[test]
+fn unterminated_code_point_escape() {
+    for src in [&"'\\u{41\nabc'"[..], "'\\u{41'", "'\\u{41\\\\x'", "'\\u{41"].iter() {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        match lexer.next() {
+            Err(Error::Syntax(msg, pos)) => {
+                assert_eq!(msg.as_ref(), "unterminated \\u{ escape, got '41'");
+                assert_eq!(pos, Position::new(1, 2));
+            }
+            res => panic!("missing `}}` not rejected in {:?}: {:?}", src, res),
+        }
+    }
+}
+
+#[test]
+fn code_point_escape_at_end_of_file() {
+    let cases = [
+        (r#""\u{41""#, "unterminated \\u{ escape, got '41'"),
+        (r#""\u{41"#, "unterminated \\u{ escape, got '41'"),
+        (r#"\u{41"#, "unterminated \\u{ escape, got '41'"),
+        (r#"`\u{41`"#, "unterminated \\u{ escape, got '41'"),
+    ];
+
+    for &(src, expected) in cases.iter() {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        match lexer.next() {
+            Err(Error::Syntax(msg, _)) => assert_eq!(msg.as_ref(), expected, "{}", src),
+            res => panic!("unterminated escape not rejected in {}: {:?}", src, res),
+        }
+    }
+}
+
+#[test]
+fn string_literal_line_terminators() {
+    for src in [&"\"abc\ndef\""[..], "'abc\r\ndef'", "\"abc\rdef\""].iter() {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        match lexer.next() {
+            Err(Error::Syntax(msg, pos)) => {
+                assert_eq!(
+                    msg.as_ref(),
+                    "line terminators are not allowed in string literals"
+                );
+                assert_eq!(pos, Position::new(1, 5));
+            }
+            res => panic!("raw line terminator not rejected in {:?}: {:?}", src, res),
+        }
+    }
+
+    // `<LS>` and `<PS>` are not rejected.
+    let mut lexer = Lexer::new("\"a\u{2028}b\u{2029}c\"".as_bytes());
+    assert_eq!(
+        lexer.next().unwrap().unwrap().kind(),
+        &TokenKind::string_literal("a\u{2028}b\u{2029}c", "\"a\u{2028}b\u{2029}c\"")
+    );
+}
+
+#[test]
+fn malformed_escapes_are_rejected_or_replaced() {
+    let src = r#""\uZZZZ""#;
+
+    let mut lexer = Lexer::new(src.as_bytes());
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("malformed escape sequence not rejected as expected")
+    {
+        assert_eq!(msg.as_ref(), "invalid Unicode escape sequence `\\uZZZZ`");
+    } else {
+        panic!("invalid error type");
+    }
+
+    let cases = [
+        (src, "\u{FFFD}"),
+        (r#""a\x4""#, "a\u{FFFD}"),
+        (r#""\u12""#, "\u{FFFD}"),
+        (r#""\u{ZZ}b""#, "\u{FFFD}b"),
+        (r#""\x41\u0042""#, "AB"),
+    ];
+
+    for &(src, expected) in cases.iter() {
+        let mut lexer = Lexer::new(src.as_bytes());
+        lexer.set_recover(true);
+
+        assert_eq!(
+            lexer.next().unwrap().unwrap().kind(),
+            &TokenKind::string_literal(expected, src),
+            "{}",
+            src
+        );
+    }
+}
+
+#[test]
+fn invalid_escape_sequence_message() {
+    let mut lexer = Lexer::new(&br#""\q""#[..]);
+    lexer.set_strict_mode(true);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("invalid escape sequence not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "invalid escape sequence `q` at line 1, column 2"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn vertical_tab_escape() {
+    let mut lexer = Lexer::new(&br#""a\vb""#[..]);
+
+    let expected = [TokenKind::string_literal("a\u{000b}b", r#""a\vb""#)];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn lone_high_surrogate_escape() {
+    let mut lexer = Lexer::new(&br#""\uD800""#[..]);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("lone surrogate not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "invalid surrogate sequence `\\uD800` in Unicode escape sequence: \
+             the high surrogate U+D800 is not followed by a low surrogate"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn surrogate_pair_escape() {
+    let mut lexer = Lexer::new(&br#""\uD83D\uDE00""#[..]);
+
+    let expected = [TokenKind::string_literal("\u{1F600}", r#""\uD83D\uDE00""#)];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn lone_low_surrogate_escape() {
+    let mut lexer = Lexer::new(&br#""\uDE00""#[..]);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("lone surrogate not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "invalid surrogate sequence `\\uDE00` in Unicode escape sequence: \
+             the low surrogate U+DE00 is not preceded by a high surrogate"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn surrogate_braced_unicode_escape() {
+    let mut lexer = Lexer::new(&br#""\u{D800}""#[..]);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("surrogate code point not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "invalid Unicode escape sequence `\\u{D800}`: U+D800 is a high surrogate code point"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn braced_escapes_do_not_combine_into_surrogate_pairs() {
+    let high_surrogate = "invalid Unicode escape sequence `\\u{D83D}`: \
+                          U+D83D is a high surrogate code point";
+    let lone_high_surrogate = "invalid surrogate sequence `\\uD83D` in Unicode escape sequence: \
+                               the high surrogate U+D83D is not followed by a low surrogate";
+
+    for &(src, expected) in &[
+        (r#""\u{D83D}\u{DE00}""#, high_surrogate),
+        (r#""\u{D83D}\uDE00""#, high_surrogate),
+        (r#""\uD83D\u{DE00}""#, lone_high_surrogate),
+    ] {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        if let Error::Syntax(msg, _) = lexer
+            .next()
+            .expect_err("surrogate pair of braced escapes not rejected as expected")
+        {
+            assert_eq!(msg.as_ref(), expected, "{}", src);
+        } else {
+            panic!("invalid error type for {}", src);
+        }
+    }
+
+    // Only two consecutive non-braced escapes make a surrogate pair, while a braced escape is the
+    // whole code point.
+    let mut lexer = Lexer::new(&br#""\uD83D\uDE00" "\u{1F600}""#[..]);
+
+    let expected = [
+        TokenKind::string_literal("\u{1F600}", r#""\uD83D\uDE00""#),
+        TokenKind::string_literal("\u{1F600}", r#""\u{1F600}""#),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn recovered_lone_surrogate_keeps_next_escape() {
+    // Looking for the low surrogate consumes the start of the next escape sequence, which must
+    // still be decoded on its own.
+    for &(src, expected) in &[
+        (r#""\uD800\n""#, "\u{FFFD}\n"),
+        (r#""\uD83D\u{41}""#, "\u{FFFD}A"),
+        (r#""\uD83D\u{D800}x""#, "\u{FFFD}\u{FFFD}x"),
+    ] {
+        let mut lexer = Lexer::new(src.as_bytes());
+        lexer.set_recover(true);
+
+        expect_tokens(&mut lexer, &[TokenKind::string_literal(expected, src)]);
+    }
+}
+
+#[test]
+fn astral_code_point_escape() {
+    let mut lexer = Lexer::new(&br#""\u{1F600}""#[..]);
+
+    let token = lexer.next().unwrap().expect("no string literal token");
+    let string = match token.kind() {
+        TokenKind::StringLiteral(string, _) => string,
+        kind => panic!("expected a string literal, got {:?}", kind),
+    };
+
+    // Strings are stored in UTF-8, so the code point is a single `char`, even though it takes two
+    // UTF-16 code units in the string seen by JavaScript.
+    let chars: Vec<char> = string.chars().collect();
+    assert_eq!(chars, ['\u{1F600}']);
+    assert_eq!(chars[0].len_utf16(), 2);
+    assert_eq!(string.encode_utf16().count(), 2);
+}
+
+#[test]
+fn hexadecimal_escape() {
+    let mut lexer = Lexer::new(&br#""\x41""#[..]);
+
+    let expected = [TokenKind::string_literal("A", r#""\x41""#)];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn hexadecimal_escapes_digit_cases() {
+    let mut lexer = Lexer::new(&br#""\x00\x09\x41\x7a\x7E\xaF\xFf""#[..]);
+
+    let expected = [TokenKind::string_literal(
+        "\u{0}\tAz~\u{af}\u{ff}",
+        r#""\x00\x09\x41\x7a\x7E\xaF\xFf""#,
+    )];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn hexadecimal_escape_before_unicode_escape() {
+    let mut lexer = Lexer::new(&br#""\xD8\u0041""#[..]);
+
+    // The code units are 0x00D8 and 0x0041: `\xD8` is not the start of a surrogate.
+    let expected = [TokenKind::string_literal("\u{D8}A", r#""\xD8\u0041""#)];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn hexadecimal_escape_does_not_complete_surrogate_pair() {
+    let mut lexer = Lexer::new(&br#""\uD83D\xDE""#[..]);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("lone surrogate not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "invalid surrogate sequence `\\uD83D` in Unicode escape sequence: \
+             the high surrogate U+D83D is not followed by a low surrogate"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn invalid_hexadecimal_escape() {
+    let mut lexer = Lexer::new(&br#""\xG1""#[..]);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("invalid hexadecimal escape not rejected as expected")
+    {
+        assert_eq!(msg.as_ref(), "invalid hexadecimal escape sequence `\\xG1`");
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn truncated_hexadecimal_escape() {
+    let mut lexer = Lexer::new(&br#""\x4""#[..]);
+
+    lexer
+        .next()
+        .expect_err("truncated hexadecimal escape not rejected as expected");
+}
+
+#[test]
+fn empty_braced_unicode_escape() {
+    let mut lexer = Lexer::new(&br#""\u{}""#[..]);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("empty Unicode escape not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "Unicode escape sequence `\\u{}` has no digits"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn out_of_range_braced_unicode_escape() {
+    let mut lexer = Lexer::new(&br#""\u{110000}""#[..]);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("out of range Unicode escape not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "invalid Unicode escape sequence `\\u{110000}`: \
+             0x110000 is above the greatest code point U+10FFFF"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+
+    let mut lexer = Lexer::new(&br#""\u{FFFFFFFFFF}""#[..]);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("overflowing Unicode escape not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "invalid Unicode escape sequence `\\u{FFFFFFFFFF}`: \
+             the code point is above the greatest code point U+10FFFF"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn max_braced_unicode_escape() {
+    let mut lexer = Lexer::new(&br#""\u{10FFFF}""#[..]);
+
+    let expected = [TokenKind::string_literal("\u{10FFFF}", r#""\u{10FFFF}""#)];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn line_continuation_positions() {
+    let mut lexer = Lexer::new(&b"\"foo\\\nbar\";"[..]);
+
+    let string = lexer.next().unwrap().unwrap();
+    assert_eq!(
+        string.kind(),
+        &TokenKind::string_literal("foobar", "\"foo\\\nbar\"")
+    );
+    assert_eq!(string.span(), span((1, 1), (2, 5)));
+    assert!(lexer.cursor.pos().line_number() > string.span().start().line_number());
+
+    assert_eq!(lexer.next().unwrap().unwrap().span(), span((2, 5), (2, 6)));
+}
+
+#[test]
+fn escape_sequence_positions() {
+    let mut lexer = Lexer::new(&br#""\x41\u0042";"#[..]);
+
+    assert_eq!(lexer.next().unwrap().unwrap().span(), span((1, 1), (1, 13)));
+    assert_eq!(
+        lexer.next().unwrap().unwrap().span(),
+        span((1, 13), (1, 14))
+    );
+}
+
+#[test]
+fn line_continuation_crlf() {
+    let mut lexer = Lexer::new(&b"\"a\\\r\nb\""[..]);
+
+    let expected = [TokenKind::string_literal("ab", "\"a\\\r\nb\"")];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn line_continuation_line_separator() {
+    let mut lexer = Lexer::new("\"a\\\u{2028}b\"".as_bytes());
+
+    let expected = [TokenKind::string_literal("ab", "\"a\\\u{2028}b\"")];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn legacy_octal_escape() {
+    let mut lexer = Lexer::new(&br#""\101" "\7" "\12" "\08""#[..]);
+
+    let expected = [
+        TokenKind::string_literal("A", r#""\101""#),
+        TokenKind::string_literal("\u{7}", r#""\7""#),
+        TokenKind::string_literal("\n", r#""\12""#),
+        TokenKind::string_literal("\u{0}8", r#""\08""#),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn legacy_octal_escape_strict() {
+    let mut lexer = Lexer::new(&br#""\101""#[..]);
+    lexer.set_strict_mode(true);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("octal escape sequence in strict mode not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "octal escape sequences are not allowed in strict mode"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn nul_escape() {
+    let mut lexer = Lexer::new(&br#""\0" "\00" "a\0b""#[..]);
+
+    let expected = [
+        TokenKind::string_literal("\u{0}", r#""\0""#),
+        TokenKind::string_literal("\u{0}", r#""\00""#),
+        TokenKind::string_literal("a\u{0}b", r#""a\0b""#),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn nul_escape_strict() {
+    let mut lexer = Lexer::new(&br#""\0""#[..]);
+    lexer.set_strict_mode(true);
+
+    expect_tokens(&mut lexer, &[TokenKind::string_literal("\u{0}", r#""\0""#)]);
+}
+
+#[test]
+fn octal_escape_warnings() {
+    let mut lexer = Lexer::new(&br#"'\7' + '\0' + '\12'"#[..]);
+
+    expect_tokens(
+        &mut lexer,
+        &[
+            TokenKind::string_literal("\u{7}", r#"'\7'"#),
+            TokenKind::Punctuator(Punctuator::Add),
+            TokenKind::string_literal("\u{0}", r#"'\0'"#),
+            TokenKind::Punctuator(Punctuator::Add),
+            TokenKind::string_literal("\n", r#"'\12'"#),
+        ],
+    );
+
+    let warnings = lexer.take_warnings();
+    let positions: Vec<_> = warnings.iter().map(Diagnostic::position).collect();
+    assert_eq!(positions, [Position::new(1, 2), Position::new(1, 16)]);
+    assert!(lexer.take_warnings().is_empty());
+}
+
+#[test]
+fn nul_escape_followed_by_digit_strict() {
+    let mut lexer = Lexer::new(&br#""\01""#[..]);
+    lexer.set_strict_mode(true);
+
+    if let Error::Syntax(msg, _) = lexer
+        .next()
+        .expect_err("`\\01` in strict mode not rejected as expected")
+    {
+        assert_eq!(
+            msg.as_ref(),
+            "octal escape sequences are not allowed in strict mode"
+        );
+    } else {
+        panic!("invalid error type");
+    }
+}
+
+#[test]
+fn unicode_escaped_identifiers() {
+    let mut lexer = Lexer::new(&br#"\u0061 \u0061bc a\u{62}c _\u{24}"#[..]);
+
+    let expected = [
+        TokenKind::identifier("a"),
+        TokenKind::identifier("abc"),
+        TokenKind::identifier("abc"),
+        TokenKind::identifier("_$"),
+    ];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
+#[test]
+fn unicode_escaped_identifier_invalid_character() {
+    let cases = [
+        (r#"a\u002E"#, "U+002E is not allowed in an identifier"),
+        (
+            r#"\u0031a"#,
+            "U+0031 is not allowed at the start of an identifier",
+        ),
+        (
+            r#"a\x41"#,
+            "only Unicode escape sequences are allowed in identifiers",
+        ),
+        (
+            r#"\u0069f"#,
+            "keyword 'if' must not contain Unicode escape sequences",
+        ),
+    ];
+
+    for &(src, expected) in cases.iter() {
+        let mut lexer = Lexer::new(src.as_bytes());
+
+        if let Error::Syntax(msg, _) = lexer
+            .next()
+            .expect_err("invalid escaped identifier not rejected as expected")
+        {
+            assert_eq!(msg.as_ref(), expected, "{}", src);
+        } else {
+            panic!("invalid error type");
+        }
+    }
+}
+
+#[test]
+fn long_string_literal_with_escapes() {
+    // The same input as the lexer benchmark, to check its decoded value.
+    let body = r"Lorem ipsum dolor sit amet\n\x41\x7e consectetur".repeat(1_000);
+    let src = format!(r#""{}""#, body);
+    let mut lexer = Lexer::new(src.as_bytes());
+
+    let value = "Lorem ipsum dolor sit amet\nA~ consectetur".repeat(1_000);
+    let expected = [TokenKind::string_literal(value, src.as_str())];
+
+    expect_tokens(&mut lexer, &expected);
+}
+
 mod carriage_return {
     use super::*;
 
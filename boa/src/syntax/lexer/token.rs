@@ -5,7 +5,7 @@
 //!
 //! [spec]: https://tc39.es/ecma262/#sec-tokens
 
-use super::regex::RegExpFlags;
+use super::{regex::RegExpFlags, template::TemplateString};
 
 use crate::{
     builtins::BigInt,
@@ -122,10 +122,20 @@ pub enum TokenKind {
     /// see: [`Punctuator`](../punc/enum.Punctuator.html)
     Punctuator(Punctuator),
 
-    /// A string literal.
-    StringLiteral(Box<str>),
+    /// A string literal, consisting of its value and of its raw source text, including the quotes.
+    StringLiteral(Box<str>, Box<str>),
 
-    TemplateLiteral(Box<str>),
+    /// A template literal without substitutions, such as `` `abc` ``.
+    TemplateLiteral(TemplateString),
+
+    /// The start of a template literal, up to its first substitution, such as `` `abc${ ``.
+    TemplateHead(TemplateString),
+
+    /// The part of a template literal between two substitutions, such as `` }abc${ ``.
+    TemplateMiddle(TemplateString),
+
+    /// The end of a template literal, after its last substitution, such as `` }abc` ``.
+    TemplateTail(TemplateString),
 
     /// A regular expression, consisting of body and flags.
     RegularExpressionLiteral(Box<str>, RegExpFlags),
@@ -199,19 +209,48 @@ impl TokenKind {
     }
 
     /// Creates a `StringLiteral` token type.
-    pub fn string_literal<S>(lit: S) -> Self
+    pub fn string_literal<S, R>(lit: S, raw: R) -> Self
     where
         S: Into<Box<str>>,
+        R: Into<Box<str>>,
     {
-        Self::StringLiteral(lit.into())
+        Self::StringLiteral(lit.into(), raw.into())
     }
 
     /// Creates a `TemplateLiteral` token type.
-    pub fn template_literal<S>(lit: S) -> Self
+    pub fn template_literal<R, C>(raw: R, cooked: C) -> Self
     where
-        S: Into<Box<str>>,
+        R: Into<Box<str>>,
+        C: Into<Box<str>>,
+    {
+        Self::TemplateLiteral(TemplateString::new(raw, cooked))
+    }
+
+    /// Creates a `TemplateHead` token type.
+    pub fn template_head<R, C>(raw: R, cooked: C) -> Self
+    where
+        R: Into<Box<str>>,
+        C: Into<Box<str>>,
+    {
+        Self::TemplateHead(TemplateString::new(raw, cooked))
+    }
+
+    /// Creates a `TemplateMiddle` token type.
+    pub fn template_middle<R, C>(raw: R, cooked: C) -> Self
+    where
+        R: Into<Box<str>>,
+        C: Into<Box<str>>,
+    {
+        Self::TemplateMiddle(TemplateString::new(raw, cooked))
+    }
+
+    /// Creates a `TemplateTail` token type.
+    pub fn template_tail<R, C>(raw: R, cooked: C) -> Self
+    where
+        R: Into<Box<str>>,
+        C: Into<Box<str>>,
     {
-        Self::TemplateLiteral(lit.into())
+        Self::TemplateTail(TemplateString::new(raw, cooked))
     }
 
     /// Creates a `RegularExpressionLiteral` token kind.
@@ -246,8 +285,11 @@ impl Display for TokenKind {
             Self::NumericLiteral(Numeric::Integer(num)) => write!(f, "{}", num),
             Self::NumericLiteral(Numeric::BigInt(ref num)) => write!(f, "{}n", num),
             Self::Punctuator(ref punc) => write!(f, "{}", punc),
-            Self::StringLiteral(ref lit) => write!(f, "{}", lit),
-            Self::TemplateLiteral(ref lit) => write!(f, "{}", lit),
+            Self::StringLiteral(ref lit, _) => write!(f, "{}", lit),
+            Self::TemplateLiteral(ref lit) => write!(f, "`{}`", lit.raw()),
+            Self::TemplateHead(ref lit) => write!(f, "`{}${{", lit.raw()),
+            Self::TemplateMiddle(ref lit) => write!(f, "}}{}${{", lit.raw()),
+            Self::TemplateTail(ref lit) => write!(f, "}}{}`", lit.raw()),
             Self::RegularExpressionLiteral(ref body, ref flags) => write!(f, "/{}/{}", body, flags),
             Self::LineTerminator => write!(f, "line terminator"),
             Self::Comment => write!(f, "comment"),
@@ -1,7 +1,7 @@
 use crate::{
     profiler::BoaProfiler,
     syntax::{
-        lexer::{InputElement, Lexer, Position, Token, TokenKind},
+        lexer::{ColumnUnit, Diagnostic, InputElement, Lexer, Position, Token, TokenKind},
         parser::error::ParseError,
     },
 };
@@ -92,6 +92,16 @@ where
         self.lexer.set_strict_mode(strict_mode)
     }
 
+    #[inline]
+    pub(super) fn set_column_unit(&mut self, column_unit: ColumnUnit) {
+        self.lexer.set_column_unit(column_unit)
+    }
+
+    #[inline]
+    pub(super) fn take_warnings(&mut self) -> Vec<Diagnostic> {
+        self.lexer.take_warnings()
+    }
+
     /// Fills the peeking buffer with the next token.
     ///
     /// It will not fill two line terminators one after the other.
@@ -4,11 +4,19 @@ mod buffered_lexer;
 use super::ParseError;
 use crate::syntax::{
     ast::Punctuator,
-    lexer::{InputElement, Lexer, Position, Token, TokenKind},
+    lexer::{ColumnUnit, Diagnostic, InputElement, Lexer, Position, Token, TokenKind},
 };
 use buffered_lexer::BufferedLexer;
 use std::io::Read;
 
+/// Default maximum number of nested brackets and template substitutions, see
+/// [`Cursor::set_max_nesting_depth`].
+///
+/// Each level takes up to about 15 KiB of stack in the recursive descent parser, so this keeps it
+/// within the 8 MiB stack of a main thread, even in debug builds. Threads that parse untrusted
+/// code need a stack as big, or a lower maximum.
+pub(super) const DEFAULT_MAX_NESTING_DEPTH: usize = 256;
+
 /// The result of a peek for a semicolon.
 #[derive(Debug)]
 pub(super) enum SemicolonResult<'s> {
@@ -22,6 +30,9 @@ pub(super) enum SemicolonResult<'s> {
 #[derive(Debug)]
 pub(super) struct Cursor<R> {
     buffered_lexer: BufferedLexer<R>,
+    /// Number of brackets and template substitutions opened and not closed yet.
+    nesting_depth: usize,
+    max_nesting_depth: usize,
 }
 
 impl<R> Cursor<R>
@@ -33,6 +44,8 @@ where
     pub(super) fn new(reader: R) -> Self {
         Self {
             buffered_lexer: Lexer::new(reader).into(),
+            nesting_depth: 0,
+            max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH,
         }
     }
 
@@ -48,7 +61,12 @@ where
 
     #[inline]
     pub(super) fn next(&mut self) -> Result<Option<Token>, ParseError> {
-        self.buffered_lexer.next(true)
+        let token = self.buffered_lexer.next(true)?;
+        if let Some(token) = &token {
+            self.track_nesting(token)?;
+        }
+
+        Ok(token)
     }
 
     #[inline]
@@ -66,6 +84,52 @@ where
         self.buffered_lexer.set_strict_mode(strict_mode)
     }
 
+    #[inline]
+    pub(super) fn set_column_unit(&mut self, column_unit: ColumnUnit) {
+        self.buffered_lexer.set_column_unit(column_unit)
+    }
+
+    #[inline]
+    pub(super) fn take_warnings(&mut self) -> Vec<Diagnostic> {
+        self.buffered_lexer.take_warnings()
+    }
+
+    /// Sets the maximum number of brackets and template substitutions nested in each other.
+    #[inline]
+    pub(super) fn set_max_nesting_depth(&mut self, max_nesting_depth: usize) {
+        self.max_nesting_depth = max_nesting_depth;
+    }
+
+    /// Keeps track of the brackets and template substitutions the consumed token opens or closes.
+    ///
+    /// The parser recurses into what they enclose, so a syntax error is returned once the maximum
+    /// nesting depth is reached, instead of letting deeply nested code overflow the stack.
+    fn track_nesting(&mut self, token: &Token) -> Result<(), ParseError> {
+        match token.kind() {
+            TokenKind::Punctuator(Punctuator::OpenParen)
+            | TokenKind::Punctuator(Punctuator::OpenBracket)
+            | TokenKind::Punctuator(Punctuator::OpenBlock)
+            | TokenKind::TemplateHead(_) => {
+                if self.nesting_depth >= self.max_nesting_depth {
+                    return Err(ParseError::general(
+                        "maximum nesting depth exceeded",
+                        token.span().start(),
+                    ));
+                }
+                self.nesting_depth += 1;
+            }
+            TokenKind::Punctuator(Punctuator::CloseParen)
+            | TokenKind::Punctuator(Punctuator::CloseBracket)
+            | TokenKind::Punctuator(Punctuator::CloseBlock)
+            | TokenKind::TemplateTail(_) => {
+                self.nesting_depth = self.nesting_depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     /// Returns an error if the next token is not of kind `kind`.
     ///
     /// Note: it will consume the next token only if the next token is the expected type.
@@ -46,6 +46,11 @@ pub enum ParseError {
         message: &'static str,
         position: Position,
     },
+    /// An error in a named source, such as a file, along with the name of the source.
+    Named {
+        name: Box<str>,
+        err: Box<ParseError>,
+    },
 }
 
 impl ParseError {
@@ -91,10 +96,37 @@ impl ParseError {
     pub(super) fn lex(e: LexError) -> Self {
         Self::Lex { err: e }
     }
-}
 
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// Gets the position of the error in the source, if it's known.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Expected { found, .. } | Self::Unexpected { found, .. } => {
+                Some(found.span().start())
+            }
+            Self::General { position, .. } => Some(*position),
+            Self::Lex { err } => err.position(),
+            Self::Named { err, .. } => err.position(),
+            Self::AbruptEnd => None,
+        }
+    }
+
+    /// Attaches the name of the source to the error, which is then formatted as
+    /// `<name>:<line>:<column>: <message>`.
+    ///
+    /// An error that already has a source name keeps it.
+    pub(super) fn with_source_name(self, name: &str) -> Self {
+        match self {
+            Self::Lex { err } => Self::lex(err.with_source_name(name)),
+            Self::Named { .. } => self,
+            e => Self::Named {
+                name: name.into(),
+                err: Box::new(e),
+            },
+        }
+    }
+
+    /// Formats the message of the error, without its position.
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Expected {
                 expected,
@@ -102,7 +134,7 @@ impl fmt::Display for ParseError {
                 context,
             } => write!(
                 f,
-                "expected {}, got '{}' in {} at line {}, col {}",
+                "expected {}, got '{}' in {}",
                 if expected.len() == 1 {
                     format!(
                         "token '{}'",
@@ -131,31 +163,115 @@ impl fmt::Display for ParseError {
                     )
                 },
                 found,
-                context,
-                found.span().start().line_number(),
-                found.span().start().column_number()
+                context
             ),
             Self::Unexpected { found, message } => write!(
                 f,
-                "unexpected token '{}'{} at line {}, col {}",
+                "unexpected token '{}'{}",
                 found,
                 if let Some(m) = message {
                     format!(", {}", m)
                 } else {
                     String::new()
-                },
-                found.span().start().line_number(),
-                found.span().start().column_number()
+                }
             ),
+            Self::General { message, .. } => f.write_str(message),
+            // The other errors don't format their position apart from the message.
+            e => fmt::Display::fmt(e, f),
+        }
+    }
+
+    /// Formats the error, followed by the offending line of `source` with a `^` under the
+    /// position of the error.
+    ///
+    /// Only the message is formatted if the position of the error is unknown.
+    pub fn to_string_with_source(&self, source: &str) -> String {
+        match self.position().and_then(|pos| source_snippet(source, pos)) {
+            Some(snippet) => format!("{}\n{}", self, snippet),
+            None => self.to_string(),
+        }
+    }
+}
+
+/// Number of characters of the offending line shown on each side of the position of an error.
+const SNIPPET_CONTEXT: usize = 40;
+
+/// Renders the line of the source at the given position, with a `^` under its column.
+///
+/// Only the characters around the column are rendered, with a `…` in place of the rest of a
+/// long line.
+fn source_snippet(source: &str, pos: Position) -> Option<String> {
+    let mut chars = source.chars().peekable();
+    for _ in 1..pos.line_number() {
+        loop {
+            match chars.next()? {
+                '\r' => {
+                    if chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                    break;
+                }
+                '\n' | '\u{2028}' | '\u{2029}' => break,
+                _ => {}
+            }
+        }
+    }
+
+    let line: Vec<char> = chars
+        .take_while(|&ch| !matches!(ch, '\r' | '\n' | '\u{2028}' | '\u{2029}'))
+        .collect();
+
+    let offset = (pos.column_number() as usize).saturating_sub(1);
+    let start = offset.saturating_sub(SNIPPET_CONTEXT).min(line.len());
+    let end = offset.saturating_add(SNIPPET_CONTEXT).min(line.len());
+
+    let mut snippet = String::new();
+    let mut indent = String::new();
+    if start > 0 {
+        snippet.push('…');
+        indent.push(' ');
+    }
+    snippet.extend(&line[start..end]);
+    if end < line.len() {
+        snippet.push('…');
+    }
+
+    // Tabs are kept, so that the caret lines up with the code whatever the tab width.
+    indent.extend(
+        line[start..]
+            .iter()
+            .chain(std::iter::repeat(&' '))
+            .take(offset - start)
+            .map(|&ch| if ch == '\t' { '\t' } else { ' ' }),
+    );
+
+    Some(format!("{}\n{}^", snippet, indent))
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
             Self::AbruptEnd => f.write_str("abrupt end"),
-            Self::General { message, position } => write!(
-                f,
-                "{} at line {}, col {}",
-                message,
-                position.line_number(),
-                position.column_number()
-            ),
             Self::Lex { err } => fmt::Display::fmt(err, f),
+            Self::Named { name, err } => match err.position() {
+                Some(pos) if !matches!(**err, Self::Lex { .. }) => {
+                    write!(f, "{}:{}: ", name, pos)?;
+                    err.fmt_message(f)
+                }
+                _ => write!(f, "{}: {}", name, err),
+            },
+            e => {
+                e.fmt_message(f)?;
+                if let Some(pos) = e.position() {
+                    write!(
+                        f,
+                        " at line {}, col {}",
+                        pos.line_number(),
+                        pos.column_number()
+                    )?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -10,12 +10,13 @@
 mod array_initializer;
 mod function_expression;
 mod object_initializer;
+mod template;
 #[cfg(test)]
 mod tests;
 
 use self::{
     array_initializer::ArrayLiteral, function_expression::FunctionExpression,
-    object_initializer::ObjectLiteral,
+    object_initializer::ObjectLiteral, template::TemplateLiteral,
 };
 use super::Expression;
 use crate::{
@@ -99,7 +100,13 @@ where
             TokenKind::BooleanLiteral(boolean) => Ok(Const::from(*boolean).into()),
             TokenKind::NullLiteral => Ok(Const::Null.into()),
             TokenKind::Identifier(ident) => Ok(Identifier::from(ident.as_ref()).into()), // TODO: IdentifierReference
-            TokenKind::StringLiteral(s) => Ok(Const::from(s.as_ref()).into()),
+            TokenKind::StringLiteral(s, _) => Ok(Const::from(s.as_ref()).into()),
+            TokenKind::TemplateLiteral(template) => Ok(Const::from(template.cooked()).into()),
+            TokenKind::TemplateHead(template) => {
+                TemplateLiteral::new(self.allow_yield, self.allow_await, template.cooked())
+                    .parse(cursor)
+                    .map(Node::from)
+            }
             TokenKind::NumericLiteral(Numeric::Integer(num)) => Ok(Const::from(*num).into()),
             TokenKind::NumericLiteral(Numeric::Rational(num)) => Ok(Const::from(*num).into()),
             TokenKind::NumericLiteral(Numeric::BigInt(num)) => Ok(Const::from(num.clone()).into()),
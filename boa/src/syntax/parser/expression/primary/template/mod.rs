@@ -0,0 +1,84 @@
+//! Template literal parsing.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript specification][spec]
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals
+//! [spec]: https://tc39.es/ecma262/#sec-template-literals
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    syntax::{
+        ast::node::{TemplateElement, TemplateLit},
+        lexer::TokenKind,
+        parser::{expression::Expression, AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
+    },
+    BoaProfiler,
+};
+
+use std::io::Read;
+
+/// Parses a template literal with substitutions, whose `TemplateHead` has already been consumed.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [ECMAScript specification][spec]
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals
+/// [spec]: https://tc39.es/ecma262/#prod-SubstitutionTemplate
+#[derive(Debug, Clone)]
+pub(super) struct TemplateLiteral {
+    allow_yield: AllowYield,
+    allow_await: AllowAwait,
+    head: Box<str>,
+}
+
+impl TemplateLiteral {
+    /// Creates a new `TemplateLiteral` parser, from the cooked string of its `TemplateHead`.
+    pub(super) fn new<Y, A, H>(allow_yield: Y, allow_await: A, head: H) -> Self
+    where
+        Y: Into<AllowYield>,
+        A: Into<AllowAwait>,
+        H: Into<Box<str>>,
+    {
+        Self {
+            allow_yield: allow_yield.into(),
+            allow_await: allow_await.into(),
+            head: head.into(),
+        }
+    }
+}
+
+impl<R> TokenParser<R> for TemplateLiteral
+where
+    R: Read,
+{
+    type Output = TemplateLit;
+
+    fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
+        let _timer = BoaProfiler::global().start_event("TemplateLiteral", "Parsing");
+        let mut elements = vec![TemplateElement::String(self.head)];
+
+        loop {
+            let expr = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
+            elements.push(TemplateElement::Expr(expr));
+
+            let tok = cursor.next()?.ok_or(ParseError::AbruptEnd)?;
+            match tok.kind() {
+                TokenKind::TemplateMiddle(template) => {
+                    elements.push(TemplateElement::String(template.cooked().into()));
+                }
+                TokenKind::TemplateTail(template) => {
+                    elements.push(TemplateElement::String(template.cooked().into()));
+                    break;
+                }
+                _ => return Err(ParseError::unexpected(tok, "template literal")),
+            }
+        }
+
+        Ok(TemplateLit::new(elements))
+    }
+}
@@ -0,0 +1,53 @@
+//! Tests for template literal parsing.
+
+use crate::syntax::{
+    ast::{
+        node::{BinOp, Identifier, TemplateElement, TemplateLit},
+        op::NumOp,
+        Const,
+    },
+    parser::tests::check_parser,
+};
+
+/// Checks a template literal without substitutions.
+#[test]
+fn check_no_substitution() {
+    check_parser("`abc`", vec![Const::from("abc").into()]);
+}
+
+/// Checks a template literal with a substitution.
+#[test]
+fn check_substitution() {
+    check_parser(
+        "`a${1 + 1}b`",
+        vec![TemplateLit::new(vec![
+            TemplateElement::String("a".into()),
+            TemplateElement::Expr(BinOp::new(NumOp::Add, Const::from(1), Const::from(1)).into()),
+            TemplateElement::String("b".into()),
+        ])
+        .into()],
+    );
+}
+
+/// Checks a template literal nested in the substitution of another one.
+#[test]
+fn check_nested() {
+    check_parser(
+        "`a${`b${c}`}${d}`",
+        vec![TemplateLit::new(vec![
+            TemplateElement::String("a".into()),
+            TemplateElement::Expr(
+                TemplateLit::new(vec![
+                    TemplateElement::String("b".into()),
+                    TemplateElement::Expr(Identifier::from("c").into()),
+                    TemplateElement::String("".into()),
+                ])
+                .into(),
+            ),
+            TemplateElement::String("".into()),
+            TemplateElement::Expr(Identifier::from("d").into()),
+            TemplateElement::String("".into()),
+        ])
+        .into()],
+    );
+}
@@ -1,4 +1,7 @@
-use crate::syntax::{ast::Const, parser::tests::check_parser};
+use crate::{
+    builtins::BigInt,
+    syntax::{ast::Const, parser::tests::check_parser},
+};
 
 #[test]
 fn check_string() {
@@ -8,3 +11,9 @@ fn check_string() {
     // Check non-empty string
     check_parser("\"hello\"", vec![Const::from("hello").into()]);
 }
+
+#[test]
+fn check_bigint() {
+    check_parser("123n", vec![Const::from(BigInt::from(123)).into()]);
+    check_parser("0xffn", vec![Const::from(BigInt::from(255)).into()]);
+}
@@ -266,7 +266,7 @@ where
                 TokenKind::Punctuator(Punctuator::CloseBlock) => {
                     return Ok(Vec::new().into());
                 }
-                TokenKind::StringLiteral(string) | TokenKind::TemplateLiteral(string) => {
+                TokenKind::StringLiteral(string, _) => {
                     if string == &"use strict".into() {
                         cursor.set_strict_mode(true);
                     }
@@ -9,7 +9,10 @@ mod statement;
 mod tests;
 
 pub use self::error::{ParseError, ParseResult};
-use crate::syntax::{ast::node::StatementList, lexer::TokenKind};
+use crate::syntax::{
+    ast::{node::StatementList, Keyword, Punctuator},
+    lexer::{ColumnUnit, Diagnostic, Lexer, TokenKind},
+};
 
 use cursor::Cursor;
 
@@ -85,6 +88,8 @@ impl From<bool> for AllowDefault {
 pub struct Parser<R> {
     /// Cursor of the parser, pointing to the lexer and used to get tokens for the parser.
     cursor: Cursor<R>,
+    /// Name of the source being parsed, attached to the syntax errors.
+    source_name: Option<Box<str>>,
 }
 
 impl<R> Parser<R> {
@@ -94,15 +99,124 @@ impl<R> Parser<R> {
     {
         Self {
             cursor: Cursor::new(reader),
+            source_name: None,
         }
     }
 
+    /// Sets the name of the source being parsed (usually a file name), so that syntax errors
+    /// report where they come from.
+    pub fn set_source_name(&mut self, name: &str) {
+        self.source_name = Some(name.into());
+    }
+
+    /// Sets how the columns of the positions in syntax errors are counted, Unicode code points by
+    /// default.
+    pub fn set_column_unit(&mut self, column_unit: ColumnUnit)
+    where
+        R: Read,
+    {
+        self.cursor.set_column_unit(column_unit)
+    }
+
+    /// Sets whether the source is parsed as strict mode code from its start, as if it began with
+    /// a `"use strict"` directive.
+    pub fn set_strict_mode(&mut self, strict_mode: bool)
+    where
+        R: Read,
+    {
+        self.cursor.set_strict_mode(strict_mode)
+    }
+
+    /// Sets the maximum number of parentheses, brackets, braces and template substitutions nested
+    /// in each other, 256 by default.
+    ///
+    /// The parser is recursive, so sources nested deeper than this are rejected with a syntax
+    /// error instead of overflowing the stack.
+    pub fn set_max_nesting_depth(&mut self, max_nesting_depth: usize)
+    where
+        R: Read,
+    {
+        self.cursor.set_max_nesting_depth(max_nesting_depth)
+    }
+
+    /// Takes the non-fatal diagnostics found in the source parsed so far.
+    pub fn take_warnings(&mut self) -> Vec<Diagnostic>
+    where
+        R: Read,
+    {
+        self.cursor.take_warnings()
+    }
+
     pub fn parse_all(&mut self) -> Result<StatementList, ParseError>
     where
         R: Read,
     {
-        Script.parse(&mut self.cursor)
+        let source_name = self.source_name.as_deref();
+        Script
+            .parse(&mut self.cursor)
+            .map_err(|e| match source_name {
+                Some(name) => e.with_source_name(name),
+                None => e,
+            })
+    }
+}
+
+/// Tells whether the given source uses module syntax, that is an `import` or `export`
+/// declaration at its top level.
+///
+/// Only the tokens are looked at, so code that does not parse may be misdetected. A source that
+/// can't even be lexed is taken as a script, so that the parser reports the error.
+pub(crate) fn uses_module_syntax<R>(reader: R) -> bool
+where
+    R: Read,
+{
+    let mut lexer = Lexer::new(reader);
+    // Number of unclosed brackets, of any kind.
+    let mut depth = 0_usize;
+    // Whether the next token starts a statement.
+    let mut statement_start = true;
+    // Whether the previous token was an `import` starting a top-level statement.
+    let mut import = false;
+
+    while let Ok(Some(token)) = lexer.next() {
+        match token.kind() {
+            TokenKind::Comment => continue,
+            TokenKind::LineTerminator => {
+                statement_start = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        // `import(...)` and `import.meta` are expressions, allowed in scripts.
+        if import {
+            match token.kind() {
+                TokenKind::Punctuator(Punctuator::OpenParen)
+                | TokenKind::Punctuator(Punctuator::Dot) => import = false,
+                _ => return true,
+            }
+        }
+
+        let top_level_statement = statement_start && depth == 0;
+        statement_start = false;
+        match token.kind() {
+            TokenKind::Keyword(Keyword::Export) if top_level_statement => return true,
+            TokenKind::Keyword(Keyword::Import) if top_level_statement => import = true,
+            TokenKind::Punctuator(Punctuator::OpenBlock)
+            | TokenKind::Punctuator(Punctuator::OpenParen)
+            | TokenKind::Punctuator(Punctuator::OpenBracket) => depth += 1,
+            TokenKind::Punctuator(Punctuator::CloseBlock) => {
+                depth = depth.saturating_sub(1);
+                statement_start = true;
+            }
+            TokenKind::Punctuator(Punctuator::CloseParen)
+            | TokenKind::Punctuator(Punctuator::CloseBracket) => depth = depth.saturating_sub(1),
+            TokenKind::Punctuator(Punctuator::Semicolon) => statement_start = true,
+            _ => {}
+        }
     }
+
+    false
 }
 
 /// Parses a full script.
@@ -123,13 +237,10 @@ where
     fn parse(self, cursor: &mut Cursor<R>) -> Result<Self::Output, ParseError> {
         match cursor.peek(0)? {
             Some(tok) => {
-                match tok.kind() {
-                    TokenKind::StringLiteral(string) | TokenKind::TemplateLiteral(string) => {
-                        if string.as_ref() == "use strict" {
-                            cursor.set_strict_mode(true);
-                        }
+                if let TokenKind::StringLiteral(string, _) = tok.kind() {
+                    if string.as_ref() == "use strict" {
+                        cursor.set_strict_mode(true);
                     }
-                    _ => {}
                 }
                 ScriptBody.parse(cursor)
             }
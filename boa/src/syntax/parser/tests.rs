@@ -1,6 +1,6 @@
 //! Tests for the parser.
 
-use super::Parser;
+use super::{uses_module_syntax, Parser};
 use crate::syntax::ast::{
     node::{
         field::GetConstField, ArrowFunctionDecl, Assign, BinOp, Call, FormalParameter,
@@ -10,6 +10,7 @@ use crate::syntax::ast::{
     op::{self, CompOp, LogOp, NumOp},
     Const,
 };
+use std::thread;
 
 /// Checks that the given JavaScript string gives the expected expression.
 #[allow(clippy::unwrap_used)]
@@ -97,6 +98,17 @@ fn hoisting() {
     );
 }
 
+#[test]
+fn byte_order_marks() {
+    check_parser(
+        "\u{FEFF}var a\u{FEFF}= 1;\u{FEFF}a++",
+        vec![
+            VarDeclList::from(vec![VarDecl::new("a", Some(Const::from(1).into()))]).into(),
+            UnaryOp::new(op::UnaryOp::IncrementPost, Identifier::from("a")).into(),
+        ],
+    );
+}
+
 #[test]
 fn ambigous_regex_divide_expression() {
     let s = "1 / a === 1 / b";
@@ -292,3 +304,135 @@ fn spread_in_arrow_function() {
         ],
     );
 }
+
+#[test]
+fn comment_terminated_by_line_separator() {
+    check_parser(
+        "a = 1 // a comment\u{2028}b = 2",
+        vec![
+            Assign::new(Identifier::from("a"), Const::from(1)).into(),
+            Assign::new(Identifier::from("b"), Const::from(2)).into(),
+        ],
+    );
+}
+
+#[test]
+fn module_syntax_detection() {
+    let modules = [
+        "export const x = 1;",
+        "import x from 'x';",
+        "import { x } from 'x';",
+        "let a = 1;\nexport { a };",
+        "if (true) {}\nimport 'x';",
+        "/* comment */ export default 1;",
+    ];
+    for src in modules.iter() {
+        assert!(uses_module_syntax(src.as_bytes()), "{}", src);
+    }
+
+    let scripts = [
+        "let x = 1;",
+        "import('x');",
+        "let meta = x.import;",
+        "let o = { export: 1 };",
+        "'export const x = 1;'",
+    ];
+    for src in scripts.iter() {
+        assert!(!uses_module_syntax(src.as_bytes()), "{}", src);
+    }
+}
+
+#[test]
+fn syntax_errors_show_the_offending_line() {
+    let src = "let a = 1;\nlet x = \"unterminated";
+
+    let error = Parser::new(src.as_bytes()).parse_all().unwrap_err();
+
+    let message = error.to_string_with_source(src);
+    assert!(
+        message.contains("\nlet x = \"unterminated\n        ^"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn syntax_errors_show_a_window_of_long_offending_lines() {
+    let src = format!("var x = {} + ) + {};", "1".repeat(50), "2".repeat(50));
+
+    let error = Parser::new(src.as_bytes()).parse_all().unwrap_err();
+
+    let message = error.to_string_with_source(&src);
+    let snippet = format!(
+        "\n…{} + ) + {}…\n{}^",
+        "1".repeat(37),
+        "2".repeat(36),
+        " ".repeat(41)
+    );
+    assert!(message.contains(&snippet), "{}", message);
+}
+
+/// Parses the given source in a thread with a stack as big as the one of a main thread, which the
+/// default maximum nesting depth is meant for, returning the error message if it doesn't parse.
+fn parse_with_main_thread_stack(src: String) -> Result<(), String> {
+    thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(move || {
+            Parser::new(src.as_bytes())
+                .parse_all()
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        })
+        .expect("could not spawn the parser thread")
+        .join()
+        .expect("the parser thread panicked")
+}
+
+#[test]
+fn deep_nesting_is_a_syntax_error() {
+    let nested = |open: &str, inner: &str, close: &str, depth: usize| {
+        format!("{}{}{}", open.repeat(depth), inner, close.repeat(depth))
+    };
+
+    for src in [
+        nested("(", "1", ")", 10_000),
+        nested("[", "1", "]", 10_000),
+        nested("{", "", "}", 10_000),
+        nested("`${", "1", "}`", 10_000),
+        nested("f(", "1", ")", 10_000),
+        nested("a[", "1", "]", 10_000),
+        nested("function f() {", "", "}", 10_000),
+    ]
+    .iter()
+    {
+        let error = parse_with_main_thread_stack(src.clone()).unwrap_err();
+        assert!(
+            error.contains("maximum nesting depth exceeded"),
+            "{}",
+            error
+        );
+    }
+
+    assert!(parse_with_main_thread_stack(nested("(", "1", ")", 200)).is_ok());
+
+    let mut parser = Parser::new(&b"((1))"[..]);
+    parser.set_max_nesting_depth(1);
+    assert!(parser.parse_all().is_err());
+    let mut parser = Parser::new(&b"((1))"[..]);
+    parser.set_max_nesting_depth(2);
+    assert!(parser.parse_all().is_ok());
+}
+
+#[test]
+fn long_chains_are_not_nesting() {
+    // Only brackets and template substitutions count as nesting, not the statements and
+    // expressions that follow each other in a chain.
+    let else_if = format!(
+        "if (x) {{}}{} else {{ 1 }}",
+        " else if (x) { 1 }".repeat(150)
+    );
+    assert_eq!(parse_with_main_thread_stack(else_if), Ok(()));
+
+    let conditional = format!("{}3", "1 ? 2 : ".repeat(150));
+    assert_eq!(parse_with_main_thread_stack(conditional), Ok(()));
+}
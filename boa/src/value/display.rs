@@ -1,9 +1,72 @@
 use super::*;
 
 /// This object is used for displaying a `Value`.
+///
+/// Nested objects are indented with 4 spaces per level by default, which can be changed with
+/// [`indent`](#method.indent). Their depth can be limited with [`max_depth`](#method.max_depth).
 #[derive(Debug, Clone, Copy)]
 pub struct ValueDisplay<'value> {
     pub(super) value: &'value Value,
+    pub(super) options: DisplayOptions,
+}
+
+impl ValueDisplay<'_> {
+    /// Sets the number of spaces that each nesting level of objects is indented with.
+    ///
+    /// With `0`, objects are printed on a single line.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    /// let value = context.eval("({ a: 1 })").unwrap();
+    ///
+    /// assert_eq!(
+    ///     value.display().indent(0).max_depth(1).to_string(),
+    ///     "{ a: 1, __proto__: {...} }"
+    /// );
+    /// ```
+    #[inline]
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.options.indent = indent;
+        self
+    }
+
+    /// Sets the number of nesting levels of objects that are printed.
+    ///
+    /// Deeper objects are printed as `{...}`.
+    #[inline]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.options.max_depth = Some(max_depth);
+        self
+    }
+}
+
+/// Options of the display of the objects in a `Value`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DisplayOptions {
+    /// Number of spaces that each nesting level is indented with.
+    indent: usize,
+    /// Number of nesting levels that are printed, if limited.
+    max_depth: Option<usize>,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            max_depth: None,
+        }
+    }
+}
+
+impl DisplayOptions {
+    /// Width of the keys of an object at the given depth, the top-level object being at depth 1.
+    ///
+    /// Keys are aligned to the right.
+    fn key_width(&self, depth: usize) -> usize {
+        self.indent * depth
+    }
 }
 
 /// A helper macro for printing objects
@@ -11,14 +74,15 @@ pub struct ValueDisplay<'value> {
 /// All of the overloads take:
 /// - The object to be printed
 /// - The function with which to print
-/// - The indentation for the current level (for nested objects)
+/// - The display options
+/// - The depth of the object (for the indentation of nested objects)
 /// - A HashSet with the addresses of the already printed objects for the current branch
 ///      (used to avoid infinite loops when there are cyclic deps)
 macro_rules! print_obj_value {
-    (all of $obj:expr, $display_fn:ident, $indent:expr, $encounters:expr) => {
+    (all of $obj:expr, $display_fn:ident, $options:expr, $depth:expr, $encounters:expr) => {
         {
-            let mut internals = print_obj_value!(internals of $obj, $display_fn, $indent, $encounters);
-            let mut props = print_obj_value!(props of $obj, $display_fn, $indent, $encounters, true);
+            let mut internals = print_obj_value!(internals of $obj, $display_fn, $options, $depth, $encounters);
+            let mut props = print_obj_value!(props of $obj, $display_fn, $options, $depth, $encounters, true);
 
             props.reserve(internals.len());
             props.append(&mut internals);
@@ -26,27 +90,27 @@ macro_rules! print_obj_value {
             props
         }
     };
-    (internals of $obj:expr, $display_fn:ident, $indent:expr, $encounters:expr) => {
+    (internals of $obj:expr, $display_fn:ident, $options:expr, $depth:expr, $encounters:expr) => {
         {
             let object = $obj.borrow();
             if object.prototype_instance().is_object() {
                 vec![format!(
                     "{:>width$}: {}",
                     "__proto__",
-                    $display_fn(object.prototype_instance(), $encounters, $indent.wrapping_add(4), true),
-                    width = $indent,
+                    $display_fn(object.prototype_instance(), $encounters, $options, $depth + 1, true),
+                    width = $options.key_width($depth),
                 )]
             } else {
                 vec![format!(
                     "{:>width$}: {}",
                     "__proto__",
                     object.prototype_instance().display(),
-                    width = $indent,
+                    width = $options.key_width($depth),
                 )]
             }
         }
     };
-    (props of $obj:expr, $display_fn:ident, $indent:expr, $encounters:expr, $print_internals:expr) => {
+    (props of $obj:expr, $display_fn:ident, $options:expr, $depth:expr, $encounters:expr, $print_internals:expr) => {
         print_obj_value!(impl $obj, |(key, val)| {
             let v = &val
                 // FIXME: handle accessor descriptors
@@ -57,8 +121,8 @@ macro_rules! print_obj_value {
             format!(
                 "{:>width$}: {}",
                 key,
-                $display_fn(v, $encounters, $indent.wrapping_add(4), $print_internals),
-                width = $indent,
+                $display_fn(v, $encounters, $options, $depth + 1, $print_internals),
+                width = $options.key_width($depth),
             )
         })
     };
@@ -74,7 +138,12 @@ macro_rules! print_obj_value {
     };
 }
 
-pub(crate) fn log_string_from(x: &Value, print_internals: bool, print_children: bool) -> String {
+pub(crate) fn log_string_from(
+    x: &Value,
+    print_internals: bool,
+    print_children: bool,
+    options: DisplayOptions,
+) -> String {
     match x {
         // We don't want to print private (compiler) or prototype properties
         Value::Object(ref v) => {
@@ -124,6 +193,7 @@ pub(crate) fn log_string_from(x: &Value, print_internals: bool, print_children:
                                         .value(),
                                     print_internals,
                                     false,
+                                    options,
                                 )
                             })
                             .collect::<Vec<String>>()
@@ -154,8 +224,8 @@ pub(crate) fn log_string_from(x: &Value, print_internals: bool, print_children:
                         let mappings = map
                             .iter()
                             .map(|(key, value)| {
-                                let key = log_string_from(key, print_internals, false);
-                                let value = log_string_from(value, print_internals, false);
+                                let key = log_string_from(key, print_internals, false, options);
+                                let value = log_string_from(value, print_internals, false, options);
                                 format!("{} → {}", key, value)
                             })
                             .collect::<Vec<String>>()
@@ -165,7 +235,7 @@ pub(crate) fn log_string_from(x: &Value, print_internals: bool, print_children:
                         format!("Map({})", size)
                     }
                 }
-                _ => display_obj(&x, print_internals),
+                _ => display_obj(&x, print_internals, options),
             }
         }
         Value::Symbol(ref symbol) => symbol.to_string(),
@@ -174,7 +244,7 @@ pub(crate) fn log_string_from(x: &Value, print_internals: bool, print_children:
 }
 
 /// A helper function for specifically printing object values
-pub(crate) fn display_obj(v: &Value, print_internals: bool) -> String {
+pub(crate) fn display_obj(v: &Value, print_internals: bool, options: DisplayOptions) -> String {
     // A simple helper for getting the address of a value
     // TODO: Find a more general place for this, as it can be used in other situations as well
     fn address_of<T>(t: &T) -> usize {
@@ -197,10 +267,18 @@ pub(crate) fn display_obj(v: &Value, print_internals: bool) -> String {
     fn display_obj_internal(
         data: &Value,
         encounters: &mut HashSet<usize>,
-        indent: usize,
+        options: DisplayOptions,
+        depth: usize,
         print_internals: bool,
     ) -> String {
         if let Value::Object(ref v) = *data {
+            if options
+                .max_depth
+                .map_or(false, |max_depth| depth > max_depth)
+            {
+                return String::from("{...}");
+            }
+
             // The in-memory address of the current object
             let addr = address_of(v.as_ref());
 
@@ -213,28 +291,34 @@ pub(crate) fn display_obj(v: &Value, print_internals: bool) -> String {
             // Mark the current object as encountered
             encounters.insert(addr);
 
-            let result = if print_internals {
-                print_obj_value!(all of v, display_obj_internal, indent, encounters).join(",\n")
+            let entries = if print_internals {
+                print_obj_value!(all of v, display_obj_internal, options, depth, encounters)
             } else {
-                print_obj_value!(props of v, display_obj_internal, indent, encounters, print_internals)
-                        .join(",\n")
+                print_obj_value!(props of v, display_obj_internal, options, depth, encounters, print_internals)
             };
 
             // If the current object is referenced in a different branch,
             // it will not cause an infinte printing loop, so it is safe to be printed again
             encounters.remove(&addr);
 
-            let closing_indent = String::from_utf8(vec![b' '; indent.wrapping_sub(4)])
-                .expect("Could not create the closing brace's indentation string");
+            if options.indent == 0 {
+                if entries.is_empty() {
+                    String::from("{}")
+                } else {
+                    format!("{{ {} }}", entries.join(", "))
+                }
+            } else {
+                let closing_indent = " ".repeat(options.key_width(depth - 1));
 
-            format!("{{\n{}\n{}}}", result, closing_indent)
+                format!("{{\n{}\n{}}}", entries.join(",\n"), closing_indent)
+            }
         } else {
             // Every other type of data is printed with the display method
             format!("{}", data.display())
         }
     }
 
-    display_obj_internal(v, &mut encounters, 4, print_internals)
+    display_obj_internal(v, &mut encounters, options, 1, print_internals)
 }
 
 impl Display for ValueDisplay<'_> {
@@ -249,7 +333,11 @@ impl Display for ValueDisplay<'_> {
             },
             Value::String(ref v) => write!(f, "\"{}\"", v),
             Value::Rational(v) => format_rational(*v, f),
-            Value::Object(_) => write!(f, "{}", log_string_from(self.value, true, true)),
+            Value::Object(_) => write!(
+                f,
+                "{}",
+                log_string_from(self.value, true, true, self.options)
+            ),
             Value::Integer(v) => write!(f, "{}", v),
             Value::BigInt(ref num) => write!(f, "{}n", num),
         }
@@ -258,8 +346,8 @@ impl Display for ValueDisplay<'_> {
 
 /// This is different from the ECMAScript compliant number to string, in the printing of `-0`.
 ///
-/// This function prints `-0` as `-0` instead of pasitive `0` as the specification says.
-/// This is done to make it easer for the user of the REPL to identify what is a `-0` vs `0`,
+/// This function prints `-0` as `-0` instead of positive `0` as the specification says.
+/// This is done to make it easier for the user of the REPL to identify what is a `-0` vs `0`,
 /// since the REPL is not bound to the ECMAScript specification we can do this.
 fn format_rational(v: f64, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     if v.is_sign_negative() && v == 0.0 {
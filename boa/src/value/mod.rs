@@ -35,6 +35,7 @@ mod rcsymbol;
 mod r#type;
 
 pub use conversions::*;
+use display::DisplayOptions;
 pub use display::ValueDisplay;
 pub use equality::*;
 pub use hash::*;
@@ -427,6 +428,15 @@ impl Value {
         }
     }
 
+    /// Converts the value to a Rust `bool`, following the `ToBoolean` abstract operation.
+    ///
+    /// This is the same as [`Value::to_boolean`], named after the Rust type for embedders. It never
+    /// calls into JavaScript, so it can't fail.
+    #[inline]
+    pub fn to_bool(&self) -> bool {
+        self.to_boolean()
+    }
+
     /// Removes a property from a Value object.
     ///
     /// It will return a boolean based on if the value was removed, if there was no value to remove false is returned.
@@ -604,7 +614,10 @@ impl Value {
     /// ```
     #[inline]
     pub fn display(&self) -> ValueDisplay<'_> {
-        ValueDisplay { value: self }
+        ValueDisplay {
+            value: self,
+            options: DisplayOptions::default(),
+        }
     }
 
     /// Converts the value to a string.
@@ -627,6 +640,22 @@ impl Value {
         }
     }
 
+    /// Converts the value to a Rust `String`, following the `ToString` abstract operation.
+    ///
+    /// Objects are converted with their `toString` or `valueOf` methods, so this can throw.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::Context;
+    /// let mut context = Context::new();
+    ///
+    /// let value = context.eval("({})").unwrap();
+    /// assert_eq!(value.to_std_string(&mut context).unwrap(), "[object Object]");
+    /// ```
+    pub fn to_std_string(&self, ctx: &mut Context) -> Result<String> {
+        self.to_string(ctx).map(|string| string.as_str().to_owned())
+    }
+
     /// Converts the value to an Object.
     ///
     /// This function is equivalent to `Object(value)` in JavaScript
@@ -828,6 +857,22 @@ impl Value {
         }
     }
 
+    /// Converts the value to a Rust `f64`, following the `ToNumber` abstract operation.
+    ///
+    /// This is the same as [`Value::to_number`], named after the Rust type for embedders.
+    ///
+    /// # Examples
+    /// ```
+    ///# use boa::{Context, Value};
+    /// let mut context = Context::new();
+    ///
+    /// assert_eq!(Value::from("42").to_f64(&mut context).unwrap(), 42.0);
+    /// ```
+    #[inline]
+    pub fn to_f64(&self, ctx: &mut Context) -> Result<f64> {
+        self.to_number(ctx)
+    }
+
     /// This is a more specialized version of `to_numeric`, including `BigInt`.
     ///
     /// This function is equivalent to `Number(value)` in JavaScript
@@ -246,6 +246,82 @@ fn to_string() {
     assert_eq!(f64_to_str(-0.0000001), "-1e-7");
 
     assert_eq!(f64_to_str(3e50), "3e+50");
+
+    // Unlike `ToString`, the display of `-0` keeps its sign.
+    assert_eq!(f64_to_str(-0.0), "-0");
+}
+
+#[test]
+fn number_to_string() {
+    let mut engine = Context::new();
+    let mut f64_to_str = |f| {
+        Value::Rational(f)
+            .to_string(&mut engine)
+            .unwrap()
+            .to_string()
+    };
+
+    assert_eq!(f64_to_str(0.1), "0.1");
+    assert_eq!(f64_to_str(-0.0), "0");
+    assert_eq!(f64_to_str(f64::NAN), "NaN");
+    assert_eq!(f64_to_str(f64::INFINITY), "Infinity");
+    assert_eq!(f64_to_str(f64::NEG_INFINITY), "-Infinity");
+
+    // Numbers below 10^21 are written without exponent, with the shortest digits that round-trip.
+    assert_eq!(f64_to_str(100000000000000000000.0), "100000000000000000000");
+    assert_eq!(f64_to_str(123456789012345680000.0), "123456789012345680000");
+    assert_eq!(f64_to_str(1e21), "1e+21");
+    assert_eq!(f64_to_str(1.5e300), "1.5e+300");
+
+    // Numbers of at least 10^-6 are written without exponent.
+    assert_eq!(f64_to_str(0.000001), "0.000001");
+    assert_eq!(f64_to_str(5e-7), "5e-7");
+    assert_eq!(f64_to_str(-1.25e-7), "-1.25e-7");
+    assert_eq!(f64_to_str(5e-324), "5e-324");
+}
+
+#[test]
+fn rust_conversions() {
+    let mut engine = Context::new();
+
+    assert_eq!(Value::from("42").to_f64(&mut engine).unwrap(), 42.0);
+    assert!(Value::from("4 2").to_f64(&mut engine).unwrap().is_nan());
+    assert_eq!(
+        Value::from(true).to_std_string(&mut engine).unwrap(),
+        "true"
+    );
+    assert!(!Value::from("").to_bool());
+    assert!(Value::from("false").to_bool());
+
+    let object = forward_val(&mut engine, "({})").unwrap();
+    assert_eq!(
+        object.to_std_string(&mut engine).unwrap(),
+        "[object Object]"
+    );
+    assert!(object.to_f64(&mut engine).unwrap().is_nan());
+    assert!(object.to_bool());
+
+    // Arrays are converted through their `join`, but are always truthy, even when empty.
+    let array = forward_val(&mut engine, "[]").unwrap();
+    assert_eq!(array.to_std_string(&mut engine).unwrap(), "");
+    assert_eq!(array.to_f64(&mut engine).unwrap(), 0.0);
+    assert!(array.to_bool());
+    let array = forward_val(&mut engine, "[42]").unwrap();
+    assert_eq!(array.to_f64(&mut engine).unwrap(), 42.0);
+
+    let object = forward_val(
+        &mut engine,
+        "({ valueOf() { return 7; }, toString() { return 'seven'; } })",
+    )
+    .unwrap();
+    assert_eq!(object.to_f64(&mut engine).unwrap(), 7.0);
+    assert_eq!(object.to_std_string(&mut engine).unwrap(), "seven");
+
+    let object = forward_val(&mut engine, "({ valueOf() { throw 1; } })").unwrap();
+    assert!(object.to_f64(&mut engine).is_err());
+    let symbol = forward_val(&mut engine, "Symbol()").unwrap();
+    assert!(symbol.to_std_string(&mut engine).is_err());
+    assert!(symbol.to_bool());
 }
 
 #[test]
@@ -444,6 +520,43 @@ fn debug_object() {
     assert_eq!(format!("{:?}", value), format!("{:?}", value));
 }
 
+#[test]
+fn display_object_compact_and_indented() {
+    let mut engine = Context::new();
+    let d_obj = r#"
+        let o = Object.create(null);
+        o.a = Object.create(null);
+        o.a.b = 1;
+        o
+    "#;
+    let value = forward_val(&mut engine, d_obj).unwrap();
+
+    assert_eq!(
+        value.display().indent(0).to_string(),
+        "{ a: { b: 1, __proto__: null }, __proto__: null }"
+    );
+    assert_eq!(
+        value.display().indent(2).to_string(),
+        "{\n a: {\n   b: 1,\n__proto__: null\n  },\n__proto__: null\n}"
+    );
+    assert_eq!(
+        value.display().indent(0).max_depth(1).to_string(),
+        "{ a: {...}, __proto__: null }"
+    );
+}
+
+#[test]
+fn display_cyclic_object() {
+    let mut engine = Context::new();
+    let value = forward_val(&mut engine, "let o = {}; o.self = o; o").unwrap();
+
+    assert!(value
+        .display()
+        .indent(0)
+        .to_string()
+        .starts_with("{ self: [Cycle], __proto__: {"));
+}
+
 #[test]
 #[ignore] // TODO: Once objects are printed in a simpler way this test can be simplified and used
 fn display_object() {
@@ -109,7 +109,7 @@ fn parse_tokens(src: &str) -> Result<StatementList, String> {
 
     Parser::new(src.as_bytes())
         .parse_all()
-        .map_err(|e| format!("ParsingError: {}", e))
+        .map_err(|e| format!("ParsingError: {}", e.to_string_with_source(src)))
 }
 
 /// Dumps the AST to stdout with format controlled by the given arguments.
@@ -136,6 +136,20 @@ fn dump(src: &str, args: &Opt) -> Result<(), String> {
     Ok(())
 }
 
+/// Evaluates the source, returning the displayed error it throws if any.
+///
+/// The thrown `SyntaxError`s only have the position of the error, so the source is parsed again
+/// to show the offending line, with a `^` under the position.
+fn eval(engine: &mut Context, src: &str) -> Result<String, String> {
+    match engine.eval(src) {
+        Ok(v) => Ok(v.display().to_string()),
+        Err(v) => Err(match engine.parse_to_ast(src) {
+            Err(e) => format!("SyntaxError: {}", e.to_string_with_source(src)),
+            Ok(_) => v.display().to_string(),
+        }),
+    }
+}
+
 pub fn main() -> Result<(), std::io::Error> {
     let args = Opt::from_args();
 
@@ -149,9 +163,9 @@ pub fn main() -> Result<(), std::io::Error> {
                 eprintln!("{}", e);
             }
         } else {
-            match engine.eval(&buffer) {
-                Ok(v) => println!("{}", v.display()),
-                Err(v) => eprintln!("Uncaught {}", v.display()),
+            match eval(&mut engine, &buffer) {
+                Ok(v) => println!("{}", v),
+                Err(v) => eprintln!("Uncaught {}", v),
             }
         }
     }
@@ -185,11 +199,9 @@ pub fn main() -> Result<(), std::io::Error> {
                             eprintln!("{}", e);
                         }
                     } else {
-                        match engine.eval(line.trim_end()) {
-                            Ok(v) => println!("{}", v.display()),
-                            Err(v) => {
-                                eprintln!("{}: {}", "Uncaught".red(), v.display().to_string().red())
-                            }
+                        match eval(&mut engine, line.trim_end()) {
+                            Ok(v) => println!("{}", v),
+                            Err(v) => eprintln!("{}: {}", "Uncaught".red(), v.red()),
                         }
                     }
                 }
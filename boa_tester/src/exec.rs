@@ -1,214 +1,2267 @@
 //! Execution module for the test runner.
 
 use super::{
-    Harness, Outcome, Phase, SuiteResult, Test, TestFlags, TestOutcomeResult, TestResult,
-    TestSuite, CLI,
+    read_failures, Harness, Outcome, Phase, SuiteResult, SuiteStream, Test, TestFlags,
+    TestOutcomeResult, TestResult, TestSuite, CLI,
+};
+use boa::{
+    parse,
+    syntax::{ast::node::StatementList, Parser},
+    Context, Value,
 };
-use boa::{parse, Context};
 use colored::Colorize;
-use fxhash::FxHashSet;
+use fxhash::{FxHashMap, FxHashSet};
 use once_cell::sync::Lazy;
-use std::{fs, panic, path::Path};
+use rayon::prelude::*;
+use regex::RegexSet;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::BTreeSet,
+    fs,
+    io::{self, Write},
+    mem, panic,
+    path::Path,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// Stack size of the threads running the tests.
+///
+/// It is the one of a main thread, which the maximum nesting depth of the parser is meant for.
+pub(super) const TEST_STACK_SIZE: usize = 8 << 20;
+
+/// Path of the list of ignored tests, relative to the working directory.
+const IGNORE_FILE: &str = "test_ignore.txt";
 
-/// List of ignored tests.
-static IGNORED: Lazy<FxHashSet<Box<str>>> = Lazy::new(|| {
-    let path = Path::new("test_ignore.txt");
+/// List of ignored tests, or why it could not be read.
+///
+/// `main` checks it with `check_ignore_file` before running the tests.
+static IGNORED: Lazy<Result<Ignored, String>> = Lazy::new(|| {
+    let path = Path::new(IGNORE_FILE);
     if path.exists() {
-        let filtered = fs::read_to_string(path).expect("could not read test filters");
-        filtered
-            .lines()
-            .filter(|line| !line.is_empty() && !line.starts_with("//"))
-            .map(|line| line.to_owned().into_boxed_str())
-            .collect::<FxHashSet<_>>()
+        let filtered = fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {}", IGNORE_FILE, e))?;
+        Ignored::new(&filtered).map_err(|e| format!("{}: {}", IGNORE_FILE, e))
     } else {
-        FxHashSet::default()
+        Ignored::new("")
     }
 });
 
-impl TestSuite {
-    /// Runs the test suite.
-    pub(crate) fn run(&self, harness: &Harness) -> SuiteResult {
-        if CLI.verbose() {
-            println!("Suite {}:", self.name);
-        }
+/// Checks that the list of ignored tests could be read, returning why it couldn't otherwise.
+pub(crate) fn check_ignore_file() -> Result<(), &'static str> {
+    IGNORED.as_ref().map(|_| ()).map_err(String::as_str)
+}
 
-        // TODO: in parallel
-        let suites: Vec<_> = self.suites.iter().map(|suite| suite.run(harness)).collect();
+/// Gets the list of ignored tests, once `check_ignore_file` succeeded.
+fn ignored() -> &'static Ignored {
+    IGNORED
+        .as_ref()
+        .expect("the list of ignored tests was not checked")
+}
 
-        // TODO: in parallel
-        let tests: Vec<_> = self.tests.iter().map(|test| test.run(harness)).collect();
+/// Paths of the tests to run again, if `--rerun-failures` was given.
+static RERUN_FAILURES: Lazy<Option<FxHashSet<Box<str>>>> = Lazy::new(|| {
+    CLI.rerun_failures()
+        .map(|path| read_failures(path).expect("could not read the previous failures"))
+});
 
-        if CLI.verbose() {
-            println!();
-        }
+/// Set of tests to ignore, as listed in `test_ignore.txt`.
+///
+/// Each line is either the exact name or path of a test, or a glob pattern matched against the
+/// test name and its path inside the `test` folder. In patterns, `*` and `?` match any characters
+/// except `/`, and `**` matches across folders.
+///
+/// Lines can end with a `//` comment, and `/* */` comments can span several lines.
+///
+/// An entry can give the outcome the tests it matches are known to have, as in
+/// `built-ins/Foo.js = panic`. Those tests still run, and only fail if their outcome changes.
+#[derive(Debug)]
+struct Ignored {
+    names: FxHashMap<Box<str>, Ignore>,
+    globs: RegexSet,
+    /// How the tests matching each pattern of `globs` are handled.
+    glob_ignores: Vec<Ignore>,
+    /// Every entry in the order of the file, with the index of its pattern in `globs` for globs.
+    entries: Vec<(Box<str>, Option<usize>)>,
+}
 
-        // Count passed tests
-        let mut passed = 0;
-        let mut ignored = 0;
-        for test in &tests {
-            match test.result {
-                TestOutcomeResult::Passed => passed += 1,
-                TestOutcomeResult::Ignored => ignored += 1,
-                _ => {}
+/// How a test listed in `test_ignore.txt` is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ignore {
+    /// The test doesn't run.
+    Skip,
+    /// The test runs, and is expected to have the given outcome.
+    Expect(TestOutcomeResult),
+}
+
+impl Ignored {
+    /// Parses the contents of a test filter file.
+    ///
+    /// If an entry is invalid, the reason is returned instead.
+    fn new(filter: &str) -> Result<Self, String> {
+        let mut names = FxHashMap::default();
+        let mut globs = Vec::new();
+        let mut glob_ignores = Vec::new();
+        let mut entries = Vec::new();
+
+        for line in filter_entries(filter) {
+            let (pattern, ignore) = match line.find('=') {
+                Some(eq) => {
+                    let outcome = parse_outcome(line[eq + 1..].trim())
+                        .map_err(|e| format!("invalid entry `{}`: {}", line, e))?;
+                    (line[..eq].trim(), Ignore::Expect(outcome))
+                }
+                None => (line, Ignore::Skip),
+            };
+
+            if pattern.contains(|c| c == '*' || c == '?') {
+                entries.push((pattern.into(), Some(globs.len())));
+                globs.push(glob_to_regex(pattern));
+                glob_ignores.push(ignore);
+            } else {
+                entries.push((pattern.into(), None));
+                names.insert(pattern.into(), ignore);
             }
         }
 
-        // Count total tests
-        let mut total = tests.len();
-        for suite in &suites {
-            total += suite.total;
-            passed += suite.passed;
-            ignored += suite.ignored;
-        }
+        Ok(Self {
+            names,
+            globs: RegexSet::new(globs).expect("could not compile test filter patterns"),
+            glob_ignores,
+            entries,
+        })
+    }
 
-        if CLI.verbose() {
-            println!(
-                "Results: total: {}, passed: {}, ignored: {}, conformance: {:.2}%",
-                total,
-                passed,
-                ignored,
-                (passed as f64 / total as f64) * 100.0
-            );
-        }
+    /// Checks if the test with the given name and path is listed, and how it must be handled.
+    ///
+    /// Exact names and paths take precedence over the glob patterns, which are tried in the
+    /// order of the file.
+    fn get(&self, name: &str, path: &str) -> Option<Ignore> {
+        self.names
+            .get(name)
+            .or_else(|| self.names.get(path))
+            .copied()
+            .or_else(|| {
+                self.globs
+                    .matches(name)
+                    .into_iter()
+                    .chain(self.globs.matches(path).into_iter())
+                    .min()
+                    .map(|glob| self.glob_ignores[glob])
+            })
+    }
 
-        SuiteResult {
-            name: self.name.clone(),
-            total,
-            passed,
-            ignored,
-            suites,
-            tests,
+    /// Checks if the test with the given name and path is listed.
+    fn contains(&self, name: &str, path: &str) -> bool {
+        self.get(name, path).is_some()
+    }
+
+    /// Gets the entries that match none of the given tests, usually because the tests they were
+    /// meant for were renamed or deleted.
+    fn stale_entries<'a, I>(&self, tests: I) -> Vec<&str>
+    where
+        I: IntoIterator<Item = &'a Test>,
+    {
+        let mut matched_names = FxHashSet::default();
+        let mut matched_globs = vec![false; self.globs.len()];
+
+        for test in tests {
+            for &key in [&*test.name, &*test.path].iter() {
+                if self.names.contains_key(key) {
+                    matched_names.insert(key);
+                }
+                for glob in self.globs.matches(key).into_iter() {
+                    matched_globs[glob] = true;
+                }
+            }
         }
+
+        self.entries
+            .iter()
+            .filter(|(entry, glob)| match glob {
+                Some(glob) => !matched_globs[*glob],
+                None => !matched_names.contains(entry.as_ref()),
+            })
+            .map(|(entry, _)| entry.as_ref())
+            .collect()
     }
 }
 
-impl Test {
-    /// Runs the test.
-    pub(crate) fn run(&self, harness: &Harness) -> TestResult {
-        // println!("Starting `{}`", self.name);
+/// Prints the entries of `test_ignore.txt` that match none of the tests of the suite.
+///
+/// In verbose mode, the ignored tests are listed too.
+pub(crate) fn list_ignored(suite: &TestSuite) {
+    let tests = suite.all_tests();
 
-        let result = if !self.flags.intersects(TestFlags::ASYNC | TestFlags::MODULE)
-            && !IGNORED.contains(&self.name)
+    if CLI.verbose() {
+        println!("Ignored tests:");
+        for test in tests
+            .iter()
+            .filter(|test| ignored().contains(&test.name, &test.path))
         {
-            let res = panic::catch_unwind(|| {
-                match self.expected_outcome {
-                    Outcome::Positive => {
-                        let mut passed = true;
+            println!("  {}", test.path);
+        }
+    }
+
+    println!("Stale ignore entries:");
+    for entry in ignored().stale_entries(tests) {
+        println!("  {}", entry);
+    }
+}
 
-                        if self.flags.contains(TestFlags::RAW) {
-                            let mut engine = self.set_up_env(&harness, false);
-                            let res = engine.eval(&self.content);
+/// Runs a single test, even if it's ignored or filtered out, and prints its outcome along with
+/// why it did not pass, if it didn't.
+///
+/// Returns whether the test passed.
+pub(crate) fn run_single(test: &Test, harness: &Harness) -> bool {
+    let ((result, result_text), _) = match CLI.timeout() {
+        Some(timeout) => test.run_with_timeout(harness, timeout),
+        None => test.execute_measured(harness),
+    };
 
-                            passed = res.is_ok()
-                        } else {
-                            if self.flags.contains(TestFlags::STRICT) {
-                                let mut engine = self.set_up_env(&harness, true);
-                                let res = engine.eval(&self.content);
+    println!("Test {}: {}", test.path, outcome_name(result));
+    if !result_text.is_empty() {
+        println!("{}", result_text);
+    }
 
-                                passed = res.is_ok()
-                            }
+    result == TestOutcomeResult::Passed
+}
 
-                            if passed && self.flags.contains(TestFlags::NO_STRICT) {
-                                let mut engine = self.set_up_env(&harness, false);
-                                let res = engine.eval(&self.content);
+/// Rewrites `test_ignore.txt` so that it lists the tests of the suite that didn't pass, keeping
+/// the comments at its top.
+pub(crate) fn update_ignore(suite: &TestSuite, results: &SuiteResult) -> io::Result<()> {
+    let path = Path::new(IGNORE_FILE);
+    let old = if path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
 
-                                passed = res.is_ok()
-                            }
-                        }
+    fs::write(path, ignore_file(ignored(), &old, suite, results))
+}
 
-                        passed
+/// Creates the content of a `test_ignore.txt` file listing the paths of the tests that failed,
+/// panicked or timed out, after the comments at the top of the `old` file.
+///
+/// The tests that were ignored or didn't run keep being ignored, with the outcome they are
+/// expected to have, if any.
+fn ignore_file(ignored: &Ignored, old: &str, suite: &TestSuite, results: &SuiteResult) -> String {
+    let outcomes: FxHashMap<_, _> = results
+        .flat_tests()
+        .map(|(_, test)| (&*test.path, test.result))
+        .collect();
+
+    let mut entries: Vec<String> = suite
+        .all_tests()
+        .into_iter()
+        .filter_map(|test| {
+            let ignore = match outcomes.get(&*test.path) {
+                Some(TestOutcomeResult::Passed) => None,
+                Some(TestOutcomeResult::Ignored) | None => ignored.get(&test.name, &test.path),
+                Some(_) => Some(Ignore::Skip),
+            };
+
+            match ignore? {
+                Ignore::Skip => Some(test.path.to_string()),
+                Ignore::Expect(outcome) => {
+                    Some(format!("{} = {}", test.path, outcome_name(outcome)))
+                }
+            }
+        })
+        .collect();
+    entries.sort();
+
+    let header: Vec<_> = old
+        .lines()
+        .take_while(|line| line.trim().is_empty() || line.trim_start().starts_with("//"))
+        .collect();
+    let header = header.join("\n");
+    let header = header.trim_end();
+
+    let mut content = String::new();
+    if !header.is_empty() {
+        content.push_str(header);
+        content.push_str("\n\n");
+    }
+    for entry in entries {
+        content.push_str(&entry);
+        content.push('\n');
+    }
+    content
+}
+
+/// Parses the outcome given to an entry of `test_ignore.txt`.
+fn parse_outcome(outcome: &str) -> Result<TestOutcomeResult, String> {
+    match outcome {
+        "pass" => Ok(TestOutcomeResult::Passed),
+        "fail" => Ok(TestOutcomeResult::Failed),
+        "panic" => Ok(TestOutcomeResult::Panic),
+        "timeout" => Ok(TestOutcomeResult::Timeout),
+        "mixed" => Ok(TestOutcomeResult::MixedStrictNonStrict),
+        "harness-error" => Ok(TestOutcomeResult::HarnessError),
+        _ => Err(format!(
+            "unknown outcome `{}`, expected one of `pass`, `fail`, `panic`, `timeout`, `mixed` or \
+             `harness-error`",
+            outcome
+        )),
+    }
+}
+
+/// Gets the name of an outcome, as written in `test_ignore.txt`.
+fn outcome_name(outcome: TestOutcomeResult) -> &'static str {
+    match outcome {
+        TestOutcomeResult::Passed => "pass",
+        TestOutcomeResult::Failed => "fail",
+        TestOutcomeResult::Panic => "panic",
+        TestOutcomeResult::Timeout => "timeout",
+        TestOutcomeResult::MixedStrictNonStrict => "mixed",
+        TestOutcomeResult::HarnessError => "harness-error",
+        TestOutcomeResult::Ignored => "ignored",
+        TestOutcomeResult::Filtered => "filtered",
+    }
+}
+
+/// Checks that a test still has the outcome `test_ignore.txt` expects from it.
+///
+/// If it does, the test is ignored. Otherwise it fails, even if it passes now, so that the entry
+/// gets updated.
+fn expect_outcome(
+    expected: TestOutcomeResult,
+    (result, result_text): (TestOutcomeResult, Box<str>),
+) -> (TestOutcomeResult, Box<str>) {
+    if result == expected {
+        return (TestOutcomeResult::Ignored, Box::default());
+    }
+
+    let mut text = format!(
+        "the outcome changed from `{}` in test_ignore.txt to `{}`",
+        outcome_name(expected),
+        outcome_name(result)
+    );
+    if !result_text.is_empty() {
+        text.push_str(": ");
+        text.push_str(&result_text);
+    }
+
+    (TestOutcomeResult::Failed, text.into())
+}
+
+/// Gets the entries of a test filter file, without the comments and the empty lines.
+fn filter_entries(filter: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut in_block_comment = false;
+
+    for mut line in filter.lines() {
+        loop {
+            if in_block_comment {
+                match line.find("*/") {
+                    Some(end) => {
+                        line = &line[end + 2..];
+                        in_block_comment = false;
                     }
-                    Outcome::Negative {
-                        phase: Phase::Parse,
-                        ref error_type,
-                    } => {
-                        assert_eq!(
-                            error_type.as_ref(),
-                            "SyntaxError",
-                            "non-SyntaxError parsing error found in {}",
-                            self.name
-                        );
-
-                        parse(&self.content).is_err()
+                    None => break,
+                }
+            } else {
+                let start = comment_start(line);
+                let entry = line[..start.unwrap_or_else(|| line.len())].trim();
+                if !entry.is_empty() {
+                    entries.push(entry);
+                }
+
+                match start {
+                    Some(start) if line[start..].starts_with("/*") => {
+                        line = &line[start + 2..];
+                        in_block_comment = true;
                     }
-                    Outcome::Negative {
-                        phase: _,
-                        error_type: _,
-                    } => {
-                        // TODO: check the phase
-                        false
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    entries
+}
+
+/// Finds the start of the first comment in a line of a test filter file.
+///
+/// Comments must be at the start of the line or after a whitespace, so that the `/*` in glob
+/// patterns like `built-ins/**/*.js` doesn't start one.
+fn comment_start(line: &str) -> Option<usize> {
+    line.match_indices('/').map(|(i, _)| i).find(|&i| {
+        matches!(line[i + 1..].chars().next(), Some('/') | Some('*'))
+            && line[..i]
+                .chars()
+                .next_back()
+                .map_or(true, char::is_whitespace)
+    })
+}
+
+/// Translates a glob pattern to an anchored regular expression.
+///
+/// `globset` would do this, but it's not one of the dependencies the tester can be built with, so
+/// the patterns are matched through the `regex` crate it already uses. Only `*`, `**` and `?` are
+/// supported.
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(ch.encode_utf8(&mut [0; 4]))),
+        }
+    }
+
+    regex.push('$');
+    regex
+}
+
+thread_local! {
+    /// Completion reported by the asynchronous test running in this thread, if any.
+    static ASYNC_COMPLETION: RefCell<Option<AsyncCompletion>> = RefCell::new(None);
+
+    /// Number of assertions made by the test running in this thread.
+    static ASSERTIONS: Cell<usize> = Cell::new(0);
+
+    /// Time spent evaluating the test running in this thread, without setting its environment
+    /// up.
+    static EVAL_TIME: Cell<Duration> = Cell::new(Duration::default());
+
+    /// Whether the code of the test running in this thread could not be parsed, in any mode.
+    static PARSE_FAILED: Cell<bool> = Cell::new(false);
+
+    /// Properties the test running in this thread added to the global object, in any mode.
+    static LEAKED_GLOBALS: RefCell<BTreeSet<Box<str>>> = RefCell::new(BTreeSet::new());
+
+    /// Harness files already parsed in this thread, by file name, along with their source.
+    ///
+    /// The AST can't be shared between threads, so each thread keeps its own cache.
+    static PARSED_HARNESS: RefCell<FxHashMap<Box<str>, CachedHarnessFile>> =
+        RefCell::new(FxHashMap::default());
+
+    /// Thread running the tests of this thread that have a timeout, started by the first of them.
+    static TIMEOUT_WORKER: RefCell<Option<TimeoutWorker>> = RefCell::new(None);
+
+    /// Interrupt of the tests running in this thread, if it's the thread of a `TimeoutWorker`.
+    static TEST_INTERRUPT: RefCell<Option<Arc<TestInterrupt>>> = RefCell::new(None);
+}
+
+/// Result of parsing a harness file.
+type ParsedHarnessFile = Result<StatementList, String>;
+
+/// A parsed harness file, along with the source it was parsed from.
+type CachedHarnessFile = (Box<str>, Rc<ParsedHarnessFile>);
+
+/// Why a single run of a test did not pass.
+#[derive(Debug, Clone, PartialEq)]
+enum RunError {
+    /// The test ran and failed, for the given reason.
+    Failed(Box<str>),
+    /// The environment of the test could not be set up, for the given reason.
+    Harness(Box<str>),
+    /// The asynchronous test never reported its completion.
+    Timeout(Box<str>),
+}
+
+/// Completion reported by an asynchronous test, through the `$DONE` callback of
+/// `doneprintHandle.js`.
+#[derive(Debug, Clone, PartialEq)]
+enum AsyncCompletion {
+    /// `$DONE()` was called without an error.
+    Complete,
+    /// `$DONE(error)` was called, with the given name and message of the error.
+    Failure(Box<str>),
+}
+
+impl AsyncCompletion {
+    /// Parses a message printed by `doneprintHandle.js`, if it is one of the
+    /// `Test262:AsyncTest*` sentinels.
+    fn parse(message: &str) -> Option<Self> {
+        if message == "Test262:AsyncTestComplete" {
+            Some(Self::Complete)
+        } else {
+            message
+                .strip_prefix("Test262:AsyncTestFailure:")
+                .map(|error| Self::Failure(error.into()))
+        }
+    }
+}
+
+/// What was measured on a run of a test.
+#[derive(Debug, Clone, Default)]
+struct Measures {
+    /// Number of assertions made by the test.
+    assertions: usize,
+    /// Time spent evaluating the test, without setting its environment up.
+    duration: Duration,
+    /// Properties the test added to the global object after its harness files ran, sorted.
+    leaked_globals: Box<[Box<str>]>,
+    /// Whether the code of the test could not be parsed.
+    parse_failed: bool,
+}
+
+/// Outcome of a run of a test, along with the reason why it did not pass and what was measured.
+type MeasuredOutcome = ((TestOutcomeResult, Box<str>), Measures);
+
+/// How long an interrupted test has to stop before its thread is abandoned.
+///
+/// The interrupt is only checked by loops and function calls, so a test stuck in a builtin can't
+/// be stopped.
+const INTERRUPT_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Result of a test run by a `TimeoutWorker`.
+#[derive(Debug)]
+enum WorkerResult {
+    /// The test finished, or was interrupted, and the worker can run the next test.
+    Ran(MeasuredOutcome),
+    /// The test could not be interrupted, so the thread of the worker is still running it.
+    Stuck(MeasuredOutcome),
+    /// The thread of the worker stopped.
+    Stopped,
+}
+
+/// Long-lived thread running tests, so that the waiting thread can give up on them once they run
+/// for too long.
+///
+/// Its harness cache is kept from one test to the next, and the tests that time out are
+/// interrupted, so that the thread can run the next ones.
+#[derive(Debug)]
+struct TimeoutWorker {
+    jobs: Option<Sender<(Test, Harness)>>,
+    results: Receiver<MeasuredOutcome>,
+    interrupt: Arc<TestInterrupt>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl TimeoutWorker {
+    /// Starts the thread of the worker.
+    fn spawn() -> Self {
+        let (jobs, received_jobs) = mpsc::channel::<(Test, Harness)>();
+        let (sender, results) = mpsc::channel();
+        let interrupt = Arc::new(TestInterrupt::default());
+
+        let worker_interrupt = interrupt.clone();
+        let thread = thread::Builder::new()
+            .stack_size(TEST_STACK_SIZE)
+            .spawn(move || {
+                TEST_INTERRUPT.with(|interrupt| *interrupt.borrow_mut() = Some(worker_interrupt));
+                for (test, harness) in received_jobs {
+                    if sender.send(test.execute_measured(&harness)).is_err() {
+                        break;
                     }
                 }
-            });
+            })
+            .expect("could not spawn the timeout worker thread");
 
-            let result = res
-                .map(|res| {
-                    if res {
-                        TestOutcomeResult::Passed
-                    } else {
-                        TestOutcomeResult::Failed
+        Self {
+            jobs: Some(jobs),
+            results,
+            interrupt,
+            thread: Some(thread),
+        }
+    }
+
+    /// Runs the test in the thread of the worker, interrupting it if it runs for longer than
+    /// `timeout`.
+    fn run(&self, test: &Test, harness: &Harness, timeout: Duration) -> WorkerResult {
+        let sent = self
+            .jobs
+            .as_ref()
+            .map(|jobs| jobs.send((test.clone(), harness.clone())));
+        if !matches!(sent, Some(Ok(()))) {
+            return WorkerResult::Stopped;
+        }
+
+        let timed_out = (
+            (TestOutcomeResult::Timeout, Box::default()),
+            Measures {
+                duration: timeout,
+                ..Measures::default()
+            },
+        );
+        match self.results.recv_timeout(timeout) {
+            Ok(result) => WorkerResult::Ran(result),
+            Err(RecvTimeoutError::Timeout) => {
+                // The outcome of the interrupted test is not the one it would have had.
+                self.interrupt.interrupt();
+                match self.results.recv_timeout(INTERRUPT_GRACE_PERIOD) {
+                    Ok(_) => {
+                        self.interrupt.reset();
+                        WorkerResult::Ran(timed_out)
                     }
-                })
-                .unwrap_or_else(|_| {
-                    eprintln!("last panic was on test \"{}\"", self.name);
-                    TestOutcomeResult::Panic
-                });
+                    Err(RecvTimeoutError::Timeout) => WorkerResult::Stuck(timed_out),
+                    Err(RecvTimeoutError::Disconnected) => WorkerResult::Stopped,
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => WorkerResult::Stopped,
+        }
+    }
+
+    /// Drops the worker without waiting for its thread, which stops once it's done with the test
+    /// it's stuck in, if ever.
+    fn abandon(mut self) {
+        self.jobs = None;
+        self.thread = None;
+    }
+}
 
-            print!(
+impl Drop for TimeoutWorker {
+    fn drop(&mut self) {
+        // The thread stops once there are no more jobs to run.
+        self.jobs = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Interrupts the contexts of a test that timed out, shared by a `TimeoutWorker` and its thread.
+#[derive(Debug, Default)]
+struct TestInterrupt {
+    state: Mutex<InterruptState>,
+}
+
+/// State of a `TestInterrupt`.
+#[derive(Debug, Default)]
+struct InterruptState {
+    /// Whether the test running now timed out.
+    timed_out: bool,
+    /// Interrupt handle of the context the test runs in.
+    context: Option<Arc<AtomicBool>>,
+}
+
+impl TestInterrupt {
+    /// Makes the interrupt stop the given context, which is interrupted right away if the test
+    /// already timed out.
+    fn watch(&self, context: &Context) {
+        let mut state = self.state.lock().expect("the interrupt state is poisoned");
+        let handle = context.interrupt_handle();
+        if state.timed_out {
+            handle.store(true, Ordering::Relaxed);
+        }
+        state.context = Some(handle);
+    }
+
+    /// Interrupts the test, along with the contexts it creates afterwards.
+    fn interrupt(&self) {
+        let mut state = self.state.lock().expect("the interrupt state is poisoned");
+        state.timed_out = true;
+        if let Some(handle) = &state.context {
+            handle.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Gets the interrupt ready for the next test.
+    fn reset(&self) {
+        *self.state.lock().expect("the interrupt state is poisoned") = InterruptState::default();
+    }
+}
+
+/// How the progress of the run is reported while the tests run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProgressStyle {
+    /// A colored dot is printed for each finished test.
+    Dots,
+    /// The counters are printed on a single line, updated as the tests finish.
+    Live,
+    /// Nothing is printed.
+    Quiet,
+}
+
+/// Live counters of the tests that already ran, shared by all the threads running them.
+#[derive(Debug)]
+pub(crate) struct Progress {
+    total: usize,
+    style: ProgressStyle,
+    done: AtomicUsize,
+    passed: AtomicUsize,
+    failed: AtomicUsize,
+    panic: AtomicUsize,
+    /// Number of panics above which the remaining tests are not run.
+    max_panics: Option<usize>,
+}
+
+impl Progress {
+    /// Number of finished tests between two updates of the progress line.
+    const UPDATE_INTERVAL: usize = 50;
+
+    /// Creates the counters for a run of `total` tests.
+    ///
+    /// The run is aborted once there are more than `max_panics` panics, if given.
+    pub(crate) fn new(total: usize, style: ProgressStyle, max_panics: Option<usize>) -> Self {
+        Self {
+            total,
+            style,
+            done: AtomicUsize::new(0),
+            passed: AtomicUsize::new(0),
+            failed: AtomicUsize::new(0),
+            panic: AtomicUsize::new(0),
+            max_panics,
+        }
+    }
+
+    /// Whether the run was aborted because of too many panics.
+    ///
+    /// The tests that didn't start yet are then left out of the results.
+    pub(crate) fn aborted(&self) -> bool {
+        self.max_panics.map_or(false, |max_panics| {
+            self.panic.load(Ordering::Relaxed) > max_panics
+        })
+    }
+
+    /// Records the outcome of a finished test, and reports it.
+    fn record(&self, result: TestOutcomeResult) {
+        match result {
+            TestOutcomeResult::Passed => self.passed.fetch_add(1, Ordering::Relaxed),
+            TestOutcomeResult::Failed
+            | TestOutcomeResult::MixedStrictNonStrict
+            | TestOutcomeResult::HarnessError
+            | TestOutcomeResult::Timeout => self.failed.fetch_add(1, Ordering::Relaxed),
+            TestOutcomeResult::Panic => self.panic.fetch_add(1, Ordering::Relaxed),
+            TestOutcomeResult::Ignored | TestOutcomeResult::Filtered => 0,
+        };
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+
+        match self.style {
+            ProgressStyle::Dots => print!(
                 "{}",
-                if let TestOutcomeResult::Passed = result {
-                    ".".green()
-                } else {
-                    ".".red()
+                match result {
+                    TestOutcomeResult::Passed => ".".green(),
+                    TestOutcomeResult::Ignored => ".".yellow(),
+                    TestOutcomeResult::Timeout => ".".magenta(),
+                    TestOutcomeResult::MixedStrictNonStrict => ".".cyan(),
+                    _ => ".".red(),
+                }
+            ),
+            ProgressStyle::Live => {
+                if done % Self::UPDATE_INTERVAL == 0 || done == self.total {
+                    print!("\r{}", self.line());
+                    let _ = io::stdout().flush();
+                }
+            }
+            ProgressStyle::Quiet => {}
+        }
+    }
+
+    /// Number of finished tests.
+    fn done(&self) -> usize {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// Progress line, such as `12345/68000 (18%) passed=10000 failed=2000 panic=5`.
+    fn line(&self) -> String {
+        let done = self.done();
+        format!(
+            "{}/{} ({}%) passed={} failed={} panic={}",
+            done,
+            self.total,
+            if self.total == 0 {
+                100
+            } else {
+                done * 100 / self.total
+            },
+            self.passed.load(Ordering::Relaxed),
+            self.failed.load(Ordering::Relaxed),
+            self.panic.load(Ordering::Relaxed),
+        )
+    }
+}
+
+impl TestSuite {
+    /// Counts the tests of the suite and its sub-suites that match the `--filter` option.
+    pub(crate) fn count_tests(&self) -> usize {
+        self.tests.iter().filter(|test| !test.is_filtered()).count()
+            + self
+                .suites
+                .iter()
+                .map(TestSuite::count_tests)
+                .sum::<usize>()
+    }
+
+    /// Gets the tests of the suite and of all its sub-suites.
+    fn all_tests(&self) -> Vec<&Test> {
+        let mut tests: Vec<_> = self.tests.iter().collect();
+        for suite in self.suites.iter() {
+            tests.extend(suite.all_tests());
+        }
+        tests
+    }
+
+    /// Runs the test suite.
+    ///
+    /// The result of each suite is written to the given stream, if any, as soon as it finishes.
+    pub(crate) fn run(
+        &self,
+        harness: &Harness,
+        progress: &Progress,
+        stream: Option<&SuiteStream>,
+    ) -> SuiteResult {
+        self.run_in("", harness, progress, stream)
+    }
+
+    /// Runs the test suite, as a sub-suite of the suite at the given path.
+    fn run_in(
+        &self,
+        parent: &str,
+        harness: &Harness,
+        progress: &Progress,
+        stream: Option<&SuiteStream>,
+    ) -> SuiteResult {
+        let start = Instant::now();
+        let path = if parent.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{}/{}", parent, self.name)
+        };
+
+        // Suites without any test to run are left out of the results.
+        let suites: Vec<_> = self
+            .suites
+            .par_iter()
+            .map(|suite| suite.run_in(&path, harness, progress, stream))
+            .filter(|suite| suite.total != 0)
+            .collect();
+
+        let tests: Vec<_> = self
+            .tests
+            .par_iter()
+            .map(|test| test.run(harness, progress))
+            .filter(|test| test.result != TestOutcomeResult::Filtered)
+            .collect();
+
+        let mut result = SuiteResult::new(self.name.clone(), suites, tests);
+        result.duration_ms = start.elapsed().as_millis() as u64;
+
+        // Suites run in parallel, so the whole summary is printed at once to keep it readable.
+        if CLI.verbose() && result.total != 0 {
+            println!(
+                "\nSuite {}: total: {}, passed: {}, ignored: {}, failed: {}, panics: {}, \
+                 timed out: {}, conformance: {:.2}%, duration: {} ms",
+                result.name,
+                result.total,
+                result.passed,
+                result.ignored,
+                result.failed,
+                result.panic,
+                result.timed_out,
+                result.conformance(),
+                result.duration_ms
+            );
+        }
+
+        if let Some(stream) = stream.filter(|_| result.total != 0) {
+            stream
+                .write(&path, &result)
+                .expect("could not write the suite results");
+        }
+
+        result
+    }
+}
+
+impl SuiteResult {
+    /// Creates the result of a suite, counting the outcomes of its tests and sub-suites.
+    fn new(name: Box<str>, suites: Vec<SuiteResult>, tests: Vec<TestResult>) -> Self {
+        let mut result = Self {
+            name,
+            total: tests.len(),
+            passed: 0,
+            ignored: 0,
+            failed: 0,
+            panic: 0,
+            timed_out: 0,
+            parse_failed: 0,
+            runtime_failed: 0,
+            duration_ms: 0,
+            suites: Vec::new(),
+            tests: Vec::new(),
+        };
+
+        for test in &tests {
+            match test.result {
+                TestOutcomeResult::Passed => result.passed += 1,
+                TestOutcomeResult::Ignored => result.ignored += 1,
+                TestOutcomeResult::Failed
+                | TestOutcomeResult::MixedStrictNonStrict
+                | TestOutcomeResult::HarnessError => {
+                    result.failed += 1;
+                    if test.parse_failed {
+                        result.parse_failed += 1;
+                    } else {
+                        result.runtime_failed += 1;
+                    }
                 }
+                TestOutcomeResult::Panic => result.panic += 1,
+                TestOutcomeResult::Timeout => result.timed_out += 1,
+                TestOutcomeResult::Filtered => {}
+            }
+        }
+
+        for suite in &suites {
+            result.total += suite.total;
+            result.passed += suite.passed;
+            result.ignored += suite.ignored;
+            result.failed += suite.failed;
+            result.panic += suite.panic;
+            result.timed_out += suite.timed_out;
+            result.parse_failed += suite.parse_failed;
+            result.runtime_failed += suite.runtime_failed;
+        }
+
+        result.suites = suites;
+        result.tests = tests;
+        result
+    }
+}
+
+impl Test {
+    /// Runs the test.
+    pub(crate) fn run(&self, harness: &Harness, progress: &Progress) -> TestResult {
+        // println!("Starting `{}`", self.name);
+
+        if self.is_filtered() || progress.aborted() {
+            return self.result(
+                TestOutcomeResult::Filtered,
+                Box::default(),
+                Measures::default(),
             );
+        }
 
-            result
+        let mut measures = Measures::default();
+        let ignore = ignored().get(&self.name, &self.path);
+        let (result, result_text) = if ignore != Some(Ignore::Skip) {
+            let outcome = retry_panics(CLI.retries(), || {
+                let (outcome, last_measures) = match CLI.timeout() {
+                    Some(timeout) => self.run_with_timeout(harness, timeout),
+                    None => self.execute_measured(harness),
+                };
+                measures = last_measures;
+                outcome
+            });
+            if !CLI.leaked_globals() {
+                measures.leaked_globals = Box::default();
+            }
+
+            if let Some(Ignore::Expect(expected)) = ignore {
+                expect_outcome(expected, outcome)
+            } else {
+                outcome
+            }
         } else {
-            // Ignoring async tests for now.
-            // TODO: implement async and add `harness/doneprintHandle.js` to the includes.
-            print!("{}", ".".yellow());
-            TestOutcomeResult::Ignored
+            (TestOutcomeResult::Ignored, Box::default())
         };
+        progress.record(result);
 
+        self.result(result, result_text, measures)
+    }
+
+    /// Creates the result of the test with the given outcome.
+    fn result(
+        &self,
+        result: TestOutcomeResult,
+        result_text: Box<str>,
+        measures: Measures,
+    ) -> TestResult {
         TestResult {
             name: self.name.clone(),
+            path: self.path.clone(),
             result,
+            result_text,
+            assertions: measures.assertions,
+            duration_ms: measures.duration.as_millis() as u64,
+            features: self.features.clone(),
+            leaked_globals: measures.leaked_globals,
+            parse_failed: measures.parse_failed,
         }
     }
 
+    /// Whether the test doesn't match the `--filter` option.
+    ///
+    /// With `--rerun-failures`, the tests that didn't fail in the previous results are filtered out
+    /// too, and so are the tests that can't run in the mode given by `--only-strict` or
+    /// `--no-strict`.
+    fn is_filtered(&self) -> bool {
+        !self.runs_in_any_mode()
+            || CLI
+                .filter()
+                .map_or(false, |filter| !filter.is_match(&self.path))
+            || RERUN_FAILURES
+                .as_ref()
+                .map_or(false, |failures| !failures.contains(&self.path))
+    }
+
+    /// Runs the test in the timeout worker of the current thread, interrupting it if it runs for
+    /// longer than `timeout`.
+    fn run_with_timeout(&self, harness: &Harness, timeout: Duration) -> MeasuredOutcome {
+        TIMEOUT_WORKER.with(|worker| {
+            let mut worker = worker.borrow_mut();
+            let result = worker
+                .get_or_insert_with(TimeoutWorker::spawn)
+                .run(self, harness, timeout);
+
+            // A new worker is started for the next test if this one can't run it.
+            match result {
+                WorkerResult::Ran(outcome) => outcome,
+                WorkerResult::Stuck(outcome) => {
+                    if let Some(worker) = worker.take() {
+                        worker.abandon();
+                    }
+                    outcome
+                }
+                WorkerResult::Stopped => {
+                    *worker = None;
+                    (
+                        (
+                            TestOutcomeResult::Panic,
+                            "the test thread stopped unexpectedly".into(),
+                        ),
+                        Measures::default(),
+                    )
+                }
+            }
+        })
+    }
+
+    /// Executes the test in the current thread, along with what was measured on its last run.
+    fn execute_measured(&self, harness: &Harness) -> MeasuredOutcome {
+        EVAL_TIME.with(Cell::take);
+        PARSE_FAILED.with(Cell::take);
+        LEAKED_GLOBALS.with(|leaked| leaked.borrow_mut().clear());
+        let outcome = self.execute(harness);
+        let measures = Measures {
+            assertions: ASSERTIONS.with(Cell::take),
+            duration: EVAL_TIME.with(Cell::take),
+            leaked_globals: LEAKED_GLOBALS
+                .with(|leaked| mem::take(&mut *leaked.borrow_mut()).into_iter().collect()),
+            parse_failed: PARSE_FAILED.with(Cell::take),
+        };
+        (outcome, measures)
+    }
+
+    /// Executes the test in the current thread.
+    ///
+    /// Along with the outcome, it returns the reason why the test did not pass, if any.
+    fn execute(&self, harness: &Harness) -> (TestOutcomeResult, Box<str>) {
+        let res = panic::catch_unwind(|| match self.expected_outcome {
+            Outcome::Positive => self.run_in_modes(|strict| self.run_once(harness, strict)),
+            // Every phase is matched explicitly, so that a new one can't go unnoticed.
+            Outcome::Negative {
+                phase,
+                ref error_type,
+            } => match phase {
+                // Boa detects the early errors while parsing.
+                Phase::Parse | Phase::Early => {
+                    self.run_in_modes(|strict| self.parse_negative(strict, error_type))
+                }
+                // Scripts have no resolution phase, their "resolution" errors are thrown when
+                // they run.
+                Phase::Resolution | Phase::Runtime => {
+                    self.run_in_modes(|strict| self.run_negative(harness, strict, error_type))
+                }
+            },
+        });
+
+        res.unwrap_or_else(|payload| {
+            eprintln!("last panic was on test \"{}\"", self.name);
+            (TestOutcomeResult::Panic, panic_message(payload.as_ref()))
+        })
+    }
+
+    /// Runs the given check in every mode required by the test flags.
+    ///
+    /// If the test must run in both strict and non-strict mode, and only passes in one of them,
+    /// the outcome is `MixedStrictNonStrict`, with the result of both runs as the text.
+    fn run_in_modes<F>(&self, run: F) -> (TestOutcomeResult, Box<str>)
+    where
+        F: Fn(bool) -> Result<(), RunError>,
+    {
+        let outcome = |res: Result<(), RunError>| match res {
+            Ok(()) => (TestOutcomeResult::Passed, Box::default()),
+            Err(RunError::Failed(text)) => (TestOutcomeResult::Failed, text),
+            Err(RunError::Harness(text)) => (TestOutcomeResult::HarnessError, text),
+            Err(RunError::Timeout(text)) => (TestOutcomeResult::Timeout, text),
+        };
+
+        if !self.flags.contains(TestFlags::NO_STRICT) {
+            // Boa has no module goal yet, so modules are evaluated as strict scripts.
+            outcome(run(true))
+        } else if !self.flags.contains(TestFlags::STRICT) {
+            outcome(run(false))
+        } else {
+            match (run(true), run(false)) {
+                (Ok(()), Ok(())) => (TestOutcomeResult::Passed, Box::default()),
+                (Err(RunError::Harness(text)), _) | (_, Err(RunError::Harness(text))) => {
+                    (TestOutcomeResult::HarnessError, text)
+                }
+                (Err(strict), Err(non_strict)) if strict == non_strict => outcome(Err(strict)),
+                (strict, non_strict) => {
+                    let text = |res: Result<(), RunError>| match res {
+                        Ok(()) => "passed".into(),
+                        Err(RunError::Failed(text))
+                        | Err(RunError::Harness(text))
+                        | Err(RunError::Timeout(text)) => text,
+                    };
+                    let outcome = if strict.is_err() && non_strict.is_err() {
+                        TestOutcomeResult::Failed
+                    } else {
+                        TestOutcomeResult::MixedStrictNonStrict
+                    };
+
+                    (
+                        outcome,
+                        format!(
+                            "strict mode: {}\nnon-strict mode: {}",
+                            text(strict),
+                            text(non_strict)
+                        )
+                        .into(),
+                    )
+                }
+            }
+        }
+    }
+
+    /// Runs the test once in a fresh environment, returning why it failed, if it did.
+    ///
+    /// Asynchronous tests only pass if they report their completion through `$DONE()`. There is
+    /// no job queue yet, so the ones that didn't call `$DONE` once evaluated never will, and
+    /// time out.
+    fn run_once(&self, harness: &Harness, strict: bool) -> Result<(), RunError> {
+        let mut engine = self.set_up_env(harness).map_err(RunError::Harness)?;
+        engine.set_strict(strict);
+        let res = self.eval(&mut engine);
+
+        let completion = ASYNC_COMPLETION.with(|completion| completion.borrow_mut().take());
+        let text: Box<str> = match res {
+            Err(e) => format!("Uncaught {}", e.display()).into(),
+            Ok(_) if self.flags.contains(TestFlags::ASYNC) => match completion {
+                Some(AsyncCompletion::Complete) => return Ok(()),
+                Some(AsyncCompletion::Failure(error)) => {
+                    format!("the test reported an asynchronous failure: {}", error).into()
+                }
+                None => {
+                    return Err(RunError::Timeout(
+                        "the test never reported its completion".into(),
+                    ))
+                }
+            },
+            Ok(_) => return Ok(()),
+        };
+
+        Err(RunError::Failed(text))
+    }
+
+    /// Runs the test once in a fresh environment, checking that it throws an error whose
+    /// constructor is named `error_type`.
+    fn run_negative(
+        &self,
+        harness: &Harness,
+        strict: bool,
+        error_type: &str,
+    ) -> Result<(), RunError> {
+        let mut engine = self.set_up_env(harness).map_err(RunError::Harness)?;
+        engine.set_strict(strict);
+
+        let text = match self.eval(&mut engine) {
+            Ok(_) => format!("no error was thrown, expected {}", error_type),
+            Err(e) => {
+                let matches = e
+                    .get_field("constructor")
+                    .get_field("name")
+                    .as_string()
+                    .map_or(false, |name| &**name == error_type);
+
+                if matches {
+                    return Ok(());
+                }
+                format!("expected {}, found Uncaught {}", error_type, e.display())
+            }
+        };
+
+        Err(RunError::Failed(text.into()))
+    }
+
+    /// Parses the test, in strict mode or not, expecting an error of the given type.
+    fn parse_negative(&self, strict: bool, error_type: &str) -> Result<(), RunError> {
+        let mut parser = Parser::new(self.content.as_bytes());
+        parser.set_strict_mode(strict);
+
+        let text = match timed(|| parser.parse_all()) {
+            Ok(_) => "the code was parsed successfully".to_owned(),
+            // The parser only reports syntax errors, other early errors are not detected yet.
+            Err(_) if error_type == "SyntaxError" => return Ok(()),
+            Err(e) => {
+                PARSE_FAILED.with(|failed| failed.set(true));
+                format!(
+                    "expected a {} while parsing, got a SyntaxError: {}",
+                    error_type, e
+                )
+            }
+        };
+
+        Err(RunError::Failed(text.into()))
+    }
+
+    /// Evaluates the test code in an environment already set up, recording the properties it adds
+    /// to the global object, and whether the code could not be parsed if it throws.
+    fn eval(&self, engine: &mut Context) -> boa::Result<Value> {
+        let globals_before = global_names(engine);
+        let res = timed(|| engine.eval(&self.content));
+
+        // Syntax errors are thrown like any other error, so the code is parsed again to tell them
+        // apart. Only the tests that throw pay for it.
+        if res.is_err() && engine.parse_to_ast(&self.content).is_err() {
+            PARSE_FAILED.with(|failed| failed.set(true));
+        }
+
+        LEAKED_GLOBALS.with(|leaked| {
+            let mut leaked = leaked.borrow_mut();
+            for name in global_names(engine) {
+                if !globals_before.contains(&name) {
+                    leaked.insert(name);
+                }
+            }
+        });
+
+        res
+    }
+
     /// Sets the environment up to run the test.
-    fn set_up_env(&self, harness: &Harness, strict: bool) -> Context {
+    ///
+    /// If a harness file can't be found or throws, the reason is returned instead.
+    fn set_up_env(&self, harness: &Harness) -> Result<Context, Box<str>> {
         // Create new Realm
-        // TODO: in parallel.
         let mut engine = Context::new();
+        TEST_INTERRUPT.with(|interrupt| {
+            if let Some(interrupt) = &*interrupt.borrow() {
+                interrupt.watch(&engine);
+            }
+        });
 
         // TODO: set up the environment.
 
-        if strict {
+        let run = |engine: &mut Context, name: &str, source: Option<&str>| {
+            let source =
+                source.ok_or_else(|| format!("could not find the {} harness file", name))?;
+            run_harness_file(engine, name, source)
+                .map(|_| ())
+                .map_err(|e| {
+                    format!(
+                        "could not run the {} harness file: Uncaught {}",
+                        name,
+                        e.display()
+                    )
+                })
+        };
+
+        run(&mut engine, "assert.js", Some(&harness.assert))?;
+        run(&mut engine, "sta.js", Some(&harness.sta))?;
+        if harness.count_assertions {
+            count_assertions(&mut engine)?;
+        }
+
+        if self.flags.contains(TestFlags::ASYNC) {
+            ASYNC_COMPLETION.with(|completion| *completion.borrow_mut() = None);
             engine
-                .eval(r#""use strict";"#)
-                .expect("could not set strict mode");
+                .register_global_function("print", 1, test262_print)
+                .expect("could not register the print() function");
+            run(
+                &mut engine,
+                "doneprintHandle.js",
+                harness.includes.get("doneprintHandle.js").map(|s| &**s),
+            )?;
+        }
+
+        for include in self.includes.iter() {
+            run(
+                &mut engine,
+                include,
+                harness.includes.get(include).map(|s| &**s),
+            )?;
+        }
+
+        Ok(engine)
+    }
+}
+
+/// Runs a harness file in the given context.
+///
+/// Harness files are the same for every test, so each of them is only parsed the first time it
+/// runs in the current thread, and the cached statements are run afterwards. A file is parsed again
+/// if its source changed, which happens when the harness is read from another folder.
+fn run_harness_file(engine: &mut Context, name: &str, source: &str) -> boa::Result<Value> {
+    let parsed = PARSED_HARNESS.with(|parsed| {
+        let mut parsed = parsed.borrow_mut();
+        match parsed.get(name) {
+            Some((cached_source, statements)) if &**cached_source == source => statements.clone(),
+            _ => {
+                let statements = Rc::new(parse(source).map_err(|e| e.to_string()));
+                parsed.insert(name.into(), (source.into(), statements.clone()));
+                statements
+            }
+        }
+    });
+
+    match *parsed {
+        Ok(ref statements) => engine.eval_ast(statements),
+        Err(ref e) => engine.throw_syntax_error(e.clone()),
+    }
+}
+
+/// Gets the names of the own properties of the global object.
+///
+/// Variables and functions declared by scripts are not properties of the global object in Boa,
+/// only the properties set on it explicitly, like `globalThis.name = value`, are.
+fn global_names(engine: &Context) -> FxHashSet<Box<str>> {
+    engine
+        .global_object()
+        .as_object()
+        .map(|global| {
+            global
+                .string_properties()
+                .map(|(name, _)| name.as_ref().into())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Runs `run`, adding the time it takes to the evaluation time of the current test.
+fn timed<F, T>(run: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let start = Instant::now();
+    let result = run();
+    EVAL_TIME.with(|time| time.set(time.get() + start.elapsed()));
+    result
+}
+
+/// Runs a test with `run`, running it again up to `retries` times while it panics.
+///
+/// Some panics depend on things like the iteration order of hash maps, so a test that passes once
+/// it is run again is recorded as passed, with a note about its flakiness.
+fn retry_panics<F>(retries: usize, mut run: F) -> (TestOutcomeResult, Box<str>)
+where
+    F: FnMut() -> (TestOutcomeResult, Box<str>),
+{
+    let (mut result, mut result_text) = run();
+    let first_panic = result_text.clone();
+
+    let mut retry = 0;
+    while result == TestOutcomeResult::Panic && retry < retries {
+        retry += 1;
+        let (new_result, new_result_text) = run();
+        result = new_result;
+        result_text = new_result_text;
+    }
+
+    if retry > 0 && result == TestOutcomeResult::Passed {
+        result_text = format!(
+            "flaky: passed after {} retries, the first run panicked: {}",
+            retry, first_panic
+        )
+        .into();
+    }
+
+    (result, result_text)
+}
+
+/// Name of the host function called by the instrumented `assert` functions.
+const COUNT_ASSERTION: &str = "$boaCountAssertion";
+
+/// Instruments the `assert` harness function and its methods, so that every assertion the test
+/// makes is counted.
+///
+/// `assert` is replaced by a wrapper, which gets the properties added to the original one. The methods
+/// are wrapped too, except for the internal ones starting with `_`. The original `assert` is kept
+/// as a property of the counter, since the wrappers can't close over it.
+///
+/// If the instrumentation fails, the reason is returned instead.
+fn count_assertions(engine: &mut Context) -> Result<(), Box<str>> {
+    ASSERTIONS.with(|assertions| assertions.set(0));
+    engine
+        .register_global_function(COUNT_ASSERTION, 0, count_assertion)
+        .map_err(|e| format!("could not register the assertion counter: {}", e.display()))?;
+
+    let assert = match engine.realm().environment.get_binding_value("assert") {
+        Some(assert) => assert,
+        None => return Ok(()),
+    };
+    // Only the properties added by the harness are enumerable, not `length` or `prototype`.
+    let properties: Vec<(String, bool)> = match assert.as_object() {
+        Some(object) => object
+            .string_properties()
+            .filter(|(_, property)| property.enumerable())
+            .map(|(name, _)| {
+                let is_method =
+                    !name.starts_with('_') && assert.get_field(name.as_str()).is_function();
+                (name.to_string(), is_method)
+            })
+            .collect(),
+        None => return Ok(()),
+    };
+
+    let wrapper = |target: &str| {
+        format!(
+            "function (a, b, c, d) {{ {0}(); return {0}.assert{1}(a, b, c, d); }}",
+            COUNT_ASSERTION, target
+        )
+    };
+    let mut source = format!(
+        "{0}.assert = assert;\nassert = {1};\n",
+        COUNT_ASSERTION,
+        wrapper("")
+    );
+    for (name, is_method) in properties {
+        let value = if is_method {
+            wrapper(&format!(".{}", name))
+        } else {
+            format!("{}.assert.{}", COUNT_ASSERTION, name)
+        };
+        source.push_str(&format!("assert.{} = {};\n", name, value));
+    }
+
+    run_harness_file(engine, "<assertion counter>", &source)
+        .map(|_| ())
+        .map_err(|e| {
+            format!(
+                "could not instrument the assert function: Uncaught {}",
+                e.display()
+            )
+            .into()
+        })
+}
+
+/// Host function counting an assertion of the test running in the current thread.
+fn count_assertion(_this: &Value, _args: &[Value], _context: &mut Context) -> boa::Result<Value> {
+    ASSERTIONS.with(|assertions| assertions.set(assertions.get() + 1));
+    Ok(Value::undefined())
+}
+
+/// Extracts the message out of the payload of a caught panic.
+fn panic_message(payload: &(dyn Any + Send)) -> Box<str> {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).into()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.as_str().into()
+    } else {
+        "panic with a non-string payload".into()
+    }
+}
+
+/// `print()` function required by the test262 asynchronous harness (`doneprintHandle.js`).
+///
+/// Asynchronous tests report their outcome by printing one of the `Test262:AsyncTest*`
+/// sentinels, which gets recorded for the test being run in the current thread. Only the first
+/// one counts, other messages are ignored.
+fn test262_print(_this: &Value, args: &[Value], context: &mut Context) -> boa::Result<Value> {
+    let message = args
+        .get(0)
+        .cloned()
+        .unwrap_or_default()
+        .to_string(context)?;
+
+    if let Some(reported) = AsyncCompletion::parse(&message) {
+        ASYNC_COMPLETION.with(|completion| {
+            completion.borrow_mut().get_or_insert(reported);
+        });
+    }
+
+    Ok(Value::undefined())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        read::{read_harness_dir, MetaData},
+        Locale,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::sync::Arc;
+
+    fn test(content: &str) -> Test {
+        let metadata = MetaData {
+            description: "".into(),
+            esid: None,
+            es5id: None,
+            es6id: None,
+            info: "".into(),
+            features: Box::default(),
+            includes: Box::default(),
+            flags: Box::default(),
+            negative: None,
+            locale: Locale::default(),
+        };
+
+        Test::new("test", "test.js", content, metadata)
+    }
+
+    fn harness() -> Harness {
+        Harness {
+            assert: "".into(),
+            sta: "".into(),
+            includes: Arc::default(),
+            count_assertions: false,
         }
+    }
+
+    #[test]
+    fn tests_restricted_to_strict_mode() {
+        let mut non_strict = Test {
+            flags: TestFlags::NO_STRICT,
+            ..test("")
+        };
+        non_strict.restrict_to_mode(true);
+        assert!(!non_strict.runs_in_any_mode());
+
+        // Legacy octal literals are only allowed in non-strict mode.
+        let mut both_modes = test("var x = 010;");
+        assert_eq!(
+            both_modes.execute(&harness()).0,
+            TestOutcomeResult::MixedStrictNonStrict
+        );
+
+        both_modes.restrict_to_mode(true);
+        assert!(both_modes.runs_in_any_mode());
+        let (result, text) = both_modes.execute(&harness());
+        assert_eq!(result, TestOutcomeResult::Failed);
+        assert!(!text.contains("non-strict mode"), "{}", text);
+    }
+
+    #[test]
+    fn tests_restricted_to_non_strict_mode() {
+        let mut strict = Test {
+            flags: TestFlags::STRICT,
+            ..test("")
+        };
+        strict.restrict_to_mode(false);
+        assert!(!strict.runs_in_any_mode());
+
+        let mut both_modes = test("var x = 010;");
+        both_modes.restrict_to_mode(false);
+        assert!(both_modes.runs_in_any_mode());
+        assert_eq!(both_modes.execute(&harness()).0, TestOutcomeResult::Passed);
+    }
+
+    #[test]
+    fn suites_and_tests_are_sorted_by_name() {
+        let named = |name: &str| Test {
+            name: name.into(),
+            ..test("")
+        };
+        let sub_suite = |name: &str| TestSuite::new(name, Vec::new(), Vec::new());
+
+        let suite = TestSuite::new(
+            "suite",
+            vec![sub_suite("b"), sub_suite("c"), sub_suite("a")],
+            vec![named("2.js"), named("10.js"), named("1.js")],
+        );
+
+        let suites: Vec<_> = suite.suites.iter().map(|suite| &*suite.name).collect();
+        assert_eq!(suites, ["a", "b", "c"]);
+        let tests: Vec<_> = suite.tests.iter().map(|test| &*test.name).collect();
+        assert_eq!(tests, ["1.js", "10.js", "2.js"]);
+    }
+
+    #[test]
+    fn shuffle_is_deterministic() {
+        let shuffled = |seed: u64| -> Vec<Box<str>> {
+            let tests = (0..20)
+                .map(|i| Test {
+                    name: format!("{}.js", i).into(),
+                    ..test("")
+                })
+                .collect();
+            let mut suite = TestSuite::new("suite", Vec::new(), tests);
+            suite.shuffle(&mut StdRng::seed_from_u64(seed));
+
+            suite.tests.iter().map(|test| test.name.clone()).collect()
+        };
+
+        assert_eq!(shuffled(1), shuffled(1));
+        assert_ne!(shuffled(1), shuffled(2));
+    }
+
+    #[test]
+    fn negative_tests_check_every_phase() {
+        let negative = |phase: Phase, error_type: &str, content: &str| Test {
+            expected_outcome: Outcome::Negative {
+                phase,
+                error_type: error_type.into(),
+            },
+            ..test(content)
+        };
 
-        engine
-            .eval(&harness.assert)
-            .expect("could not run assert.js");
-        engine.eval(&harness.sta).expect("could not run sta.js");
+        let cases = [
+            (Phase::Parse, "SyntaxError", "var = 1;"),
+            (Phase::Early, "SyntaxError", "var = 1;"),
+            (Phase::Resolution, "ReferenceError", "undeclared;"),
+            (Phase::Runtime, "TypeError", "undefined();"),
+        ];
+        for &(phase, error_type, content) in cases.iter() {
+            let test = negative(phase, error_type, content);
+            assert_eq!(
+                test.execute(&harness()).0,
+                TestOutcomeResult::Passed,
+                "{:?}",
+                phase
+            );
 
-        self.includes.iter().for_each(|include| {
-            let res = engine.eval(
-                &harness
-                    .includes
-                    .get(include)
-                    .expect("could not find include file"),
+            let test = negative(phase, error_type, "var x = 1;");
+            assert_eq!(
+                test.execute(&harness()).0,
+                TestOutcomeResult::Failed,
+                "{:?}",
+                phase
             );
-            if let Err(e) = res {
-                eprintln!("could not run the {} include file.", include);
-                panic!("Uncaught {}", e.display());
+        }
+    }
+
+    #[test]
+    fn progress_counts_finished_tests() {
+        let tests = [
+            test("var x = 1;"),
+            test("throw new Error('failed');"),
+            test("var y = 2;"),
+        ];
+        // The last test is a panicking one, that can't be run here.
+        let progress = Progress::new(tests.len() + 1, ProgressStyle::Live, None);
+
+        for test in &tests {
+            progress.record(test.execute(&harness()).0);
+        }
+        progress.record(TestOutcomeResult::Panic);
+
+        assert_eq!(progress.done(), 4);
+        assert!(!progress.aborted());
+        assert_eq!(progress.line(), "4/4 (100%) passed=2 failed=1 panic=1");
+    }
+
+    #[test]
+    fn result_keeps_the_full_path() {
+        let test = Test {
+            path: "built-ins/Array/from/source-length.js".into(),
+            ..test("")
+        };
+
+        let json = serde_json::to_string(&test.result(
+            TestOutcomeResult::Failed,
+            "".into(),
+            Measures::default(),
+        ))
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"n":"test","p":"built-ins/Array/from/source-length.js","r":"F"}"#
+        );
+
+        let json = serde_json::to_string(&test.result(
+            TestOutcomeResult::Passed,
+            "".into(),
+            Measures {
+                assertions: 3,
+                duration: Duration::from_millis(5),
+                leaked_globals: Box::default(),
+                parse_failed: false,
+            },
+        ))
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"n":"test","p":"built-ins/Array/from/source-length.js","r":"O","a":3}"#
+        );
+
+        let json = serde_json::to_string(&test.result(
+            TestOutcomeResult::Passed,
+            "".into(),
+            Measures {
+                leaked_globals: vec!["leaked".into()].into(),
+                ..Measures::default()
+            },
+        ))
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"n":"test","p":"built-ins/Array/from/source-length.js","r":"O","g":["leaked"]}"#
+        );
+    }
+
+    #[test]
+    fn suite_result_counts_outcomes() {
+        let tests = [
+            TestOutcomeResult::Passed,
+            TestOutcomeResult::Ignored,
+            TestOutcomeResult::Failed,
+            TestOutcomeResult::MixedStrictNonStrict,
+            TestOutcomeResult::Panic,
+            TestOutcomeResult::Timeout,
+        ]
+        .iter()
+        .map(|&result| TestResult {
+            name: "test".into(),
+            path: "test.js".into(),
+            result,
+            result_text: Box::default(),
+            assertions: 0,
+            duration_ms: 0,
+            features: Box::default(),
+            leaked_globals: Box::default(),
+            parse_failed: result == TestOutcomeResult::Failed,
+        })
+        .collect();
+
+        let sub_suite = SuiteResult::new("sub".into(), Vec::new(), tests);
+        let suite = SuiteResult::new("suite".into(), vec![sub_suite], Vec::new());
+
+        assert_eq!(suite.total, 6);
+        assert_eq!(suite.passed, 1);
+        assert_eq!(suite.ignored, 1);
+        assert_eq!(suite.failed, 2);
+        assert_eq!(suite.panic, 1);
+        assert_eq!(suite.timed_out, 1);
+        assert_eq!(suite.parse_failed, 1);
+        assert_eq!(suite.runtime_failed, 1);
+
+        let json = serde_json::to_string(&suite).unwrap();
+        assert!(json
+            .starts_with(r#"{"n":"suite","c":6,"p":1,"i":1,"f":2,"pn":1,"to":1,"pf":1,"rf":1,"#));
+    }
+
+    #[test]
+    fn timeout_stops_waiting_for_endless_tests() {
+        let ((result, _), _) =
+            test("while (true) {}").run_with_timeout(&harness(), Duration::from_secs(1));
+        assert_eq!(result, TestOutcomeResult::Timeout);
+    }
+
+    #[test]
+    fn timeout_worker_runs_tests_after_a_timeout() {
+        let endless = test("while (true) {}");
+        for _ in 0..2 {
+            let ((result, _), _) = endless.run_with_timeout(&harness(), Duration::from_millis(100));
+            assert_eq!(result, TestOutcomeResult::Timeout);
+        }
+
+        let ((result, _), _) =
+            test("var x = 1;").run_with_timeout(&harness(), Duration::from_secs(1));
+        assert_eq!(result, TestOutcomeResult::Passed);
+    }
+
+    #[test]
+    fn timeout_keeps_results_of_quick_tests() {
+        let ((result, _), _) =
+            test("var x = 1;").run_with_timeout(&harness(), Duration::from_secs(1));
+        assert_eq!(result, TestOutcomeResult::Passed);
+    }
+
+    #[test]
+    fn strict_and_non_strict_disagree() {
+        // Legacy octal escapes are only allowed in non-strict code.
+        let (result, result_text) = test(r#"var s = "\101";"#).execute(&harness());
+
+        assert_eq!(result, TestOutcomeResult::MixedStrictNonStrict);
+        assert!(result_text.starts_with("strict mode: Uncaught \"SyntaxError\""));
+        assert!(result_text.contains("octal escape sequences are not allowed in strict mode"));
+        assert!(result_text.ends_with("\nnon-strict mode: passed"));
+    }
+
+    /// Gets the sorted names of the global properties of the context.
+    fn global_names(engine: &Context) -> Vec<String> {
+        let mut names: Vec<_> = engine
+            .global_object()
+            .as_object()
+            .expect("the global object is not an object")
+            .keys()
+            .map(|key| key.to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn cached_harness_matches_eval() {
+        let harness = Harness {
+            assert: "function assert(value) { if (value !== true) throw new Test262Error(); }"
+                .into(),
+            sta: "function Test262Error(message) { this.message = message; }".into(),
+            includes: Arc::default(),
+            count_assertions: false,
+        };
+        let test = test("");
+
+        let mut uncached = Context::new();
+        uncached.eval(&harness.assert).unwrap();
+        uncached.eval(&harness.sta).unwrap();
+
+        // The first set up parses the harness, the second one uses the cached statements.
+        let _ = test.set_up_env(&harness).unwrap();
+        let mut cached = test.set_up_env(&harness).unwrap();
+
+        assert_eq!(global_names(&cached), global_names(&uncached));
+        assert_eq!(
+            cached.eval("typeof assert + typeof Test262Error").unwrap(),
+            Value::from("functionfunction")
+        );
+    }
+
+    #[test]
+    fn async_outcomes() {
+        let harness = read_harness_dir(Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/test262/harness"
+        )))
+        .unwrap();
+        let async_test = |content| Test {
+            flags: TestFlags::ASYNC | TestFlags::NO_STRICT,
+            ..test(content)
+        };
+
+        let (result, _) = async_test("$DONE();").execute(&harness);
+        assert_eq!(result, TestOutcomeResult::Passed);
+
+        let (result, result_text) =
+            async_test("$DONE(new TypeError('expected failure'));").execute(&harness);
+        assert_eq!(result, TestOutcomeResult::Failed);
+        assert_eq!(
+            &*result_text,
+            "the test reported an asynchronous failure: TypeError: expected failure"
+        );
+
+        let (result, result_text) = async_test("1 + 1;").execute(&harness);
+        assert_eq!(result, TestOutcomeResult::Timeout);
+        assert_eq!(&*result_text, "the test never reported its completion");
+
+        // Only the first completion counts.
+        let (result, _) = async_test("$DONE(); $DONE('too late');").execute(&harness);
+        assert_eq!(result, TestOutcomeResult::Passed);
+    }
+
+    #[test]
+    fn async_completion_sentinels() {
+        assert_eq!(
+            AsyncCompletion::parse("Test262:AsyncTestComplete"),
+            Some(AsyncCompletion::Complete)
+        );
+        assert_eq!(
+            AsyncCompletion::parse("Test262:AsyncTestFailure:Test262Error: oops"),
+            Some(AsyncCompletion::Failure("Test262Error: oops".into()))
+        );
+        assert_eq!(AsyncCompletion::parse("Test262:AsyncTestComplete!"), None);
+        assert_eq!(AsyncCompletion::parse("Test262:AsyncTestFailure"), None);
+        assert_eq!(AsyncCompletion::parse("some output"), None);
+    }
+
+    #[test]
+    fn broken_include_is_a_harness_error() {
+        let mut includes = FxHashMap::default();
+        includes.insert("broken.js".into(), "throw new Error('broken');".into());
+        let harness = Harness {
+            includes: Arc::new(includes),
+            ..harness()
+        };
+        let with_include = |include: &str| Test {
+            includes: vec![include.into()].into_boxed_slice(),
+            ..test("")
+        };
+
+        let (result, result_text) = with_include("broken.js").execute(&harness);
+        assert_eq!(result, TestOutcomeResult::HarnessError);
+        assert!(
+            result_text.starts_with("could not run the broken.js harness file: Uncaught"),
+            "{}",
+            result_text
+        );
+
+        let (result, result_text) = with_include("missing.js").execute(&harness);
+        assert_eq!(result, TestOutcomeResult::HarnessError);
+        assert_eq!(&*result_text, "could not find the missing.js harness file");
+
+        // The other tests still run.
+        let (result, _) = test("").execute(&harness);
+        assert_eq!(result, TestOutcomeResult::Passed);
+    }
+
+    #[test]
+    fn harness_is_read_from_the_harness_dir() {
+        let dir = std::env::temp_dir().join(format!("boa_tester_harness_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("assert.js"), "var fromAssert = 'custom assert';").unwrap();
+
+        let error = read_harness_dir(&dir).expect_err("missing sta.js not rejected as expected");
+        assert!(
+            error.to_string().contains("has no sta.js file"),
+            "{}",
+            error
+        );
+
+        fs::write(dir.join("sta.js"), "var fromSta = 'custom sta';").unwrap();
+        fs::write(dir.join("custom.js"), "var fromInclude = 'custom include';").unwrap();
+        let harness = read_harness_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let test = Test {
+            includes: vec!["custom.js".into()].into_boxed_slice(),
+            ..test(
+                "if (fromAssert !== 'custom assert' || fromSta !== 'custom sta' \
+                     || fromInclude !== 'custom include') {
+                    throw new Error('the harness files were not evaluated');
+                }",
+            )
+        };
+        let (result, result_text) = test.execute(&harness);
+        assert_eq!(result, TestOutcomeResult::Passed, "{}", result_text);
+    }
+
+    #[test]
+    fn assertions_are_counted() {
+        let harness = Harness {
+            assert: include_str!("../tests/fixtures/test262/harness/assert.js").into(),
+            sta: include_str!("../tests/fixtures/test262/harness/sta.js").into(),
+            includes: Arc::default(),
+            count_assertions: true,
+        };
+
+        let ((result, _), measures) = test(
+            "assert(true);
+            assert.sameValue(1, 1);
+            assert.sameValue('a', 'a', 'message');",
+        )
+        .execute_measured(&harness);
+        assert_eq!(result, TestOutcomeResult::Passed);
+        assert_eq!(measures.assertions, 3);
+
+        // The failing assertion is counted too.
+        let ((result, _), measures) =
+            test("assert(true); assert.sameValue(1, 2);").execute_measured(&harness);
+        assert_eq!(result, TestOutcomeResult::Failed);
+        assert_eq!(measures.assertions, 2);
+
+        // Without counting, `assert` is left as the harness defined it.
+        let harness = Harness {
+            count_assertions: false,
+            ..harness
+        };
+        let ((result, result_text), measures) =
+            test("assert.sameValue(assert.sameValue.length, 3);").execute_measured(&harness);
+        assert_eq!(result, TestOutcomeResult::Passed, "{}", result_text);
+        assert_eq!(measures.assertions, 0);
+    }
+
+    #[test]
+    fn uninstrumentable_assert_is_a_harness_error() {
+        let harness = Harness {
+            assert: "function assert() {} assert['not an identifier'] = 1;".into(),
+            count_assertions: true,
+            ..harness()
+        };
+
+        let (result, result_text) = test("").execute(&harness);
+        assert_eq!(result, TestOutcomeResult::HarnessError);
+        assert!(
+            result_text.starts_with("could not instrument the assert function"),
+            "{}",
+            result_text
+        );
+    }
+
+    #[test]
+    fn leaked_globals_are_recorded() {
+        let harness = Harness {
+            assert: "globalThis.fromHarness = 1;".into(),
+            ..harness()
+        };
+
+        let ((result, _), measures) = test(
+            "var declared = 1;
+            globalThis.leaked = 1;
+            this.alsoLeaked = 2;",
+        )
+        .execute_measured(&harness);
+        assert_eq!(result, TestOutcomeResult::Passed);
+        assert_eq!(
+            &*measures.leaked_globals,
+            ["alsoLeaked".into(), "leaked".into()]
+        );
+
+        let (_, measures) = test("var declared = 1;").execute_measured(&harness);
+        assert!(measures.leaked_globals.is_empty());
+    }
+
+    #[test]
+    fn panic_message_is_captured() {
+        // Accessor properties are not implemented yet, so reading one panics.
+        let (result, result_text) = test(
+            r#"var o = {};
+            Object.defineProperty(o, "x", { get: function() { return 1; } });
+            o.x;"#,
+        )
+        .execute(&harness());
+
+        assert_eq!(result, TestOutcomeResult::Panic);
+        assert_eq!(result_text.as_ref(), "not yet implemented");
+    }
+
+    #[test]
+    fn panics_are_retried() {
+        // The outcome flips between the runs, like a panic that depends on hash ordering.
+        let flaky = |panics: usize| {
+            let mut runs = 0;
+            move || {
+                runs += 1;
+                if runs <= panics {
+                    (TestOutcomeResult::Panic, "flake".into())
+                } else {
+                    (TestOutcomeResult::Passed, Box::default())
+                }
             }
+        };
+
+        let (result, result_text) = retry_panics(0, flaky(1));
+        assert_eq!(result, TestOutcomeResult::Panic);
+        assert_eq!(result_text.as_ref(), "flake");
+
+        let (result, result_text) = retry_panics(3, flaky(2));
+        assert_eq!(result, TestOutcomeResult::Passed);
+        assert_eq!(
+            result_text.as_ref(),
+            "flaky: passed after 2 retries, the first run panicked: flake"
+        );
+
+        let (result, _) = retry_panics(1, flaky(2));
+        assert_eq!(result, TestOutcomeResult::Panic);
+
+        // Tests that don't panic run only once.
+        let mut runs = 0;
+        let (result, result_text) = retry_panics(3, || {
+            runs += 1;
+            (TestOutcomeResult::Failed, "failure".into())
         });
+        assert_eq!(
+            (result, result_text.as_ref(), runs),
+            (TestOutcomeResult::Failed, "failure", 1)
+        );
+    }
+
+    #[test]
+    fn ignore_exact_name() {
+        let ignored = Ignored::new("// comment\ntco-non-eval-function\n").unwrap();
+
+        assert!(ignored.contains("tco-non-eval-function", "language/tco-non-eval-function.js"));
+        assert!(!ignored.contains("tco-non-eval-global", "language/tco-non-eval-global.js"));
+        assert!(!ignored.contains("// comment", "// comment"));
+    }
+
+    #[test]
+    fn ignore_reports_stale_entries() {
+        let ignored = Ignored::new(
+            "language/removed.js\n\
+             kept\n\
+             built-ins/Foo/**\n\
+             built-ins/Removed/**\n",
+        )
+        .unwrap();
+        let tests = vec![
+            Test {
+                name: "kept".into(),
+                path: "language/kept.js".into(),
+                ..test("")
+            },
+            Test {
+                name: "bar".into(),
+                path: "built-ins/Foo/bar.js".into(),
+                ..test("")
+            },
+        ];
+        let suite = TestSuite::new("test", Vec::new(), tests);
+
+        assert_eq!(
+            ignored.stale_entries(suite.all_tests()),
+            vec!["language/removed.js", "built-ins/Removed/**"]
+        );
+    }
+
+    #[test]
+    fn ignore_with_comments() {
+        let filter = "built-ins/Foo.js // flaky, see #123\n\
+                      /* a comment\n\
+                      spanning lines */ bar\n\
+                      language/**/*.js\n";
+        let ignored = Ignored::new(filter).unwrap();
+
+        assert_eq!(
+            filter_entries(filter),
+            ["built-ins/Foo.js", "bar", "language/**/*.js"]
+        );
+        assert!(ignored.contains("Foo", "built-ins/Foo.js"));
+        assert!(ignored.contains("bar", "built-ins/bar.js"));
+        assert!(!ignored.contains("spanning", "built-ins/spanning.js"));
+        assert!(!ignored.contains("flaky,", "built-ins/flaky,.js"));
+    }
+
+    #[test]
+    fn ignore_suffix_glob() {
+        let ignored = Ignored::new("tco-*").unwrap();
+
+        assert!(ignored.contains("tco-non-eval-function", "language/tco-non-eval-function.js"));
+        assert!(ignored.contains("tco-non-eval-global", "language/tco-non-eval-global.js"));
+        assert!(!ignored.contains("S15.10_A1", "built-ins/RegExp/S15.10_A1.js"));
+    }
+
+    #[test]
+    fn ignore_directory_glob() {
+        let ignored = Ignored::new("built-ins/RegExp/**").unwrap();
+
+        assert!(ignored.contains("S15.10_A1", "built-ins/RegExp/S15.10_A1.js"));
+        assert!(ignored.contains(
+            "character-class-escape",
+            "built-ins/RegExp/property-escapes/character-class-escape.js"
+        ));
+        assert!(!ignored.contains("S15.5_A1", "built-ins/String/S15.5_A1.js"));
+        assert!(!ignored.contains("RegExp", "built-ins/RegExp.js"));
+    }
+
+    #[test]
+    fn ignore_with_expected_outcomes() {
+        let filter = "built-ins/Foo.js = panic\n\
+                      bar = fail // throws the wrong error\n\
+                      language/** = timeout\n\
+                      skipped\n";
+        let ignored = Ignored::new(filter).unwrap();
+
+        assert_eq!(
+            ignored.get("Foo", "built-ins/Foo.js"),
+            Some(Ignore::Expect(TestOutcomeResult::Panic))
+        );
+        assert_eq!(
+            ignored.get("bar", "language/bar.js"),
+            Some(Ignore::Expect(TestOutcomeResult::Failed))
+        );
+        assert_eq!(
+            ignored.get("baz", "language/baz.js"),
+            Some(Ignore::Expect(TestOutcomeResult::Timeout))
+        );
+        assert_eq!(ignored.get("skipped", "skipped.js"), Some(Ignore::Skip));
+        assert_eq!(ignored.get("other", "built-ins/other.js"), None);
+
+        let suite = TestSuite::new("test", Vec::new(), Vec::new());
+        assert_eq!(
+            ignored.stale_entries(suite.all_tests()),
+            vec!["built-ins/Foo.js", "bar", "language/**", "skipped"]
+        );
+    }
+
+    #[test]
+    fn unknown_ignore_outcome_is_an_error() {
+        let error = Ignored::new("kept\nbuilt-ins/** = pas // typo\n")
+            .err()
+            .expect("unknown outcome not rejected as expected");
+
+        assert!(
+            error.starts_with("invalid entry `built-ins/** = pas`: unknown outcome `pas`"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn ignore_file_lists_the_failures() {
+        let ignored =
+            Ignored::new("a/skipped.js\nbuilt-ins/** = panic // slow\na/fixed.js\n").unwrap();
+        let test_at = |path: &str| Test {
+            name: path
+                .rsplit('/')
+                .next()
+                .unwrap()
+                .trim_end_matches(".js")
+                .into(),
+            path: path.into(),
+            ..test("")
+        };
+        let tests = vec![
+            test_at("a/pass.js"),
+            test_at("a/fail.js"),
+            test_at("a/skipped.js"),
+            test_at("a/fixed.js"),
+            test_at("built-ins/not-run.js"),
+        ];
+        let outcomes = [
+            TestOutcomeResult::Passed,
+            TestOutcomeResult::Panic,
+            TestOutcomeResult::Ignored,
+            TestOutcomeResult::Passed,
+        ];
+        let results = SuiteResult::new(
+            "test".into(),
+            Vec::new(),
+            tests
+                .iter()
+                .zip(outcomes.iter())
+                .map(|(test, &outcome)| test.result(outcome, Box::default(), Measures::default()))
+                .collect(),
+        );
+        let suite = TestSuite::new("test", Vec::new(), tests);
+
+        assert_eq!(
+            ignore_file(
+                &ignored,
+                "// Header.\n\n// More.\na/skipped.js\n",
+                &suite,
+                &results
+            ),
+            "// Header.\n\n// More.\n\na/fail.js\na/skipped.js\nbuilt-ins/not-run.js = panic\n"
+        );
+        assert_eq!(
+            ignore_file(&ignored, "", &suite, &results),
+            "a/fail.js\na/skipped.js\nbuilt-ins/not-run.js = panic\n"
+        );
+    }
+
+    #[test]
+    fn expected_outcome_is_ignored() {
+        let outcome = test("throw new Error('not implemented');").execute(&harness());
+
+        assert_eq!(
+            expect_outcome(TestOutcomeResult::Failed, outcome),
+            (TestOutcomeResult::Ignored, Box::default())
+        );
+    }
+
+    #[test]
+    fn changed_outcome_is_reported() {
+        let outcome = test("").execute(&harness());
+
+        assert_eq!(
+            expect_outcome(TestOutcomeResult::Panic, outcome),
+            (
+                TestOutcomeResult::Failed,
+                "the outcome changed from `panic` in test_ignore.txt to `pass`".into()
+            )
+        );
 
-        engine
+        let outcome = test("throw 1;").execute(&harness());
+        let (result, text) = expect_outcome(TestOutcomeResult::Timeout, outcome);
+        assert_eq!(result, TestOutcomeResult::Failed);
+        assert_eq!(
+            &*text,
+            "the outcome changed from `timeout` in test_ignore.txt to `fail`: Uncaught 1"
+        );
     }
 }
@@ -41,16 +41,29 @@ mod read;
 mod results;
 
 use self::{
-    read::{read_global_suite, read_harness, MetaData, Negative, TestFlag},
-    results::write_json,
+    exec::{
+        check_ignore_file, list_ignored, run_single, update_ignore, Progress, ProgressStyle,
+        TEST_STACK_SIZE,
+    },
+    read::{read_global_suite, read_harness, read_test, MetaData, Negative, TestFlag},
+    results::{
+        check_results, compare, print_comparison, print_leaked_globals, print_slowest_tests,
+        read_failures, write_comparison, write_features, write_html, write_json, write_markdown,
+        OutputFormat, SuiteStream,
+    },
 };
 use bitflags::bitflags;
 use fxhash::FxHashMap;
 use once_cell::sync::Lazy;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{
     fs,
     path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
 };
 use structopt::StructOpt;
 
@@ -69,9 +82,130 @@ struct Cli {
     #[structopt(long, parse(from_os_str), default_value = "./test262")]
     test262_path: PathBuf,
 
+    /// Optional folder to read `assert.js`, `sta.js` and the include files from, instead of the
+    /// `harness` folder of the Test262 suite.
+    #[structopt(long, parse(from_os_str))]
+    harness_dir: Option<PathBuf>,
+
     /// Optional output folder for the full results information.
     #[structopt(short, long, parse(from_os_str))]
     output: Option<PathBuf>,
+
+    /// Maximum number of threads used to run the tests (defaults to the number of CPUs).
+    #[structopt(short = "j", long)]
+    threads: Option<usize>,
+
+    /// Optional maximum time in seconds that a single test is allowed to run.
+    #[structopt(long)]
+    timeout: Option<u64>,
+
+    /// Optional regular expression to only run the tests whose path matches it.
+    #[structopt(long, parse(try_from_str = Regex::new))]
+    filter: Option<Regex>,
+
+    /// Optional format of the results of the runs kept in the output folder, `json` (the
+    /// default) or `ndjson` to stream the result of each suite as soon as it finishes.
+    #[structopt(long, requires = "output")]
+    output_format: Option<OutputFormat>,
+
+    /// Whether to write a Markdown summary next to the JSON output files.
+    #[structopt(long, requires = "output")]
+    markdown: bool,
+
+    /// Whether to write an HTML report next to the JSON output files.
+    #[structopt(long, requires = "output")]
+    html: bool,
+
+    /// Optional path to a previous `latest.json` file to compare the new results with.
+    #[structopt(long, parse(from_os_str))]
+    compare: Option<PathBuf>,
+
+    /// Optional path to a JSON file to write the comparison with the previous results to.
+    #[structopt(long, parse(from_os_str), requires = "compare")]
+    compare_output: Option<PathBuf>,
+
+    /// Whether to show a live progress counter instead of the dots, if the output is a terminal.
+    #[structopt(long)]
+    progress: bool,
+
+    /// When to color the output: `auto` only colors it if it is a terminal.
+    #[structopt(long, default_value = "auto", possible_values = &["auto", "always", "never"])]
+    color: ColorChoice,
+
+    /// Whether to hide the dots, or the progress counter, while the tests run.
+    #[structopt(short, long, conflicts_with = "progress")]
+    quiet: bool,
+
+    /// Optional path to a previous `latest.json` file, to only run the tests that failed in it.
+    #[structopt(long, parse(from_os_str))]
+    rerun_failures: Option<PathBuf>,
+
+    /// Optional seed to shuffle the tests of each suite with, to find order dependencies.
+    #[structopt(long)]
+    shuffle: Option<u64>,
+
+    /// Optional number of panics above which the run is aborted, with an error code.
+    #[structopt(long)]
+    max_panics: Option<usize>,
+
+    /// Number of times a test that panicked is run again before recording the panic.
+    #[structopt(long, default_value = "0")]
+    retries: usize,
+
+    /// Whether to exit with an error code if any test panicked.
+    #[structopt(long)]
+    fail_on_panic: bool,
+
+    /// Whether to list the entries of `test_ignore.txt` that match no test, instead of running
+    /// the tests.
+    #[structopt(long)]
+    list_ignored: bool,
+
+    /// Whether to rewrite `test_ignore.txt` after the run, so that it lists the tests that didn't
+    /// pass. It must be confirmed with `--confirm`.
+    #[structopt(long, requires = "confirm")]
+    update_ignore: bool,
+
+    /// Confirms that `--update-ignore` can overwrite `test_ignore.txt`.
+    #[structopt(long)]
+    confirm: bool,
+
+    /// Optional minimum conformance percentage, below which the tester exits with an error code.
+    #[structopt(long)]
+    min_conformance: Option<f64>,
+
+    /// Whether to report the properties each test adds to the global object, after its harness
+    /// files ran.
+    #[structopt(long)]
+    leaked_globals: bool,
+
+    /// Whether to count the assertions each test makes, by wrapping the `assert` harness function
+    /// and its methods, which changes their `length`.
+    #[structopt(long)]
+    count_assertions: bool,
+
+    /// Whether to only run the tests in strict mode, skipping the tests that can't run in it.
+    #[structopt(long, conflicts_with = "no-strict")]
+    only_strict: bool,
+
+    /// Whether to only run the tests in non-strict mode, skipping the tests that can't run in it.
+    #[structopt(long)]
+    no_strict: bool,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+/// Commands of the tester, which runs the whole suite without any.
+#[derive(StructOpt, Debug)]
+enum Command {
+    /// Runs a single test file, even if it's ignored, and prints its outcome along with why it
+    /// did not pass.
+    Run {
+        /// Path to the test file.
+        #[structopt(parse(from_os_str))]
+        path: PathBuf,
+    },
 }
 
 impl Cli {
@@ -85,14 +219,182 @@ impl Cli {
         self.test262_path.as_path()
     }
 
+    /// Folder to read the harness files from.
+    fn harness_dir(&self) -> PathBuf {
+        self.harness_dir
+            .clone()
+            .unwrap_or_else(|| self.test262_path.join("harness"))
+    }
+
     /// Optional output folder for the full results information.
     fn output(&self) -> Option<&Path> {
         self.output.as_deref()
     }
+
+    /// Format of the results of the runs kept in the output folder.
+    fn output_format(&self) -> OutputFormat {
+        self.output_format.unwrap_or(OutputFormat::Json)
+    }
+
+    /// Maximum number of threads used to run the tests.
+    fn threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    /// Optional maximum time that a single test is allowed to run.
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    /// Optional regular expression to only run the tests whose path matches it.
+    fn filter(&self) -> Option<&Regex> {
+        self.filter.as_ref()
+    }
+
+    /// Whether to write a Markdown summary next to the JSON output files.
+    fn markdown(&self) -> bool {
+        self.markdown
+    }
+
+    /// Whether to write an HTML report next to the JSON output files.
+    fn html(&self) -> bool {
+        self.html
+    }
+
+    /// Optional path to a previous results file to compare with.
+    fn compare(&self) -> Option<&Path> {
+        self.compare.as_deref()
+    }
+
+    /// Optional path to a JSON file to write the comparison to.
+    fn compare_output(&self) -> Option<&Path> {
+        self.compare_output.as_deref()
+    }
+
+    /// Whether to show a live progress counter instead of the dots.
+    fn progress(&self) -> bool {
+        self.progress
+    }
+
+    /// When to color the output.
+    fn color(&self) -> ColorChoice {
+        self.color
+    }
+
+    /// Whether to hide the dots, or the progress counter, while the tests run.
+    fn quiet(&self) -> bool {
+        self.quiet
+    }
+
+    /// Optional path to a previous results file, to only run the tests that failed in it.
+    fn rerun_failures(&self) -> Option<&Path> {
+        self.rerun_failures.as_deref()
+    }
+
+    /// Optional seed to shuffle the tests of each suite with.
+    fn shuffle(&self) -> Option<u64> {
+        self.shuffle
+    }
+
+    /// Optional number of panics above which the run is aborted.
+    fn max_panics(&self) -> Option<usize> {
+        self.max_panics
+    }
+
+    /// Number of times a test that panicked is run again.
+    fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Whether to exit with an error code if any test panicked.
+    fn fail_on_panic(&self) -> bool {
+        self.fail_on_panic
+    }
+
+    /// Whether to list the stale entries of `test_ignore.txt` instead of running the tests.
+    fn list_ignored(&self) -> bool {
+        self.list_ignored
+    }
+
+    /// Whether to rewrite `test_ignore.txt` with the tests that didn't pass.
+    fn update_ignore(&self) -> bool {
+        self.update_ignore
+    }
+
+    /// Optional minimum conformance percentage.
+    fn min_conformance(&self) -> Option<f64> {
+        self.min_conformance
+    }
+
+    /// Whether to report the properties each test adds to the global object.
+    fn leaked_globals(&self) -> bool {
+        self.leaked_globals
+    }
+
+    /// Whether to count the assertions each test makes.
+    fn count_assertions(&self) -> bool {
+        self.count_assertions
+    }
+
+    /// Optional mode to restrict the tests to, `true` for strict mode.
+    fn strict_mode(&self) -> Option<bool> {
+        if self.only_strict {
+            Some(true)
+        } else if self.no_strict {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Optional command to run instead of the whole suite.
+    fn command(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+}
+
+/// When the output is colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorChoice {
+    /// Only if the standard output is a terminal.
+    Auto,
+    /// Always, even if the output is not a terminal.
+    Always,
+    /// Never.
+    Never,
+}
+
+impl ColorChoice {
+    /// Enables or disables the colors of all the output.
+    fn apply(self) {
+        match self {
+            Self::Always => colored::control::set_override(true),
+            Self::Never => colored::control::set_override(false),
+            // The `CLICOLOR` and `NO_COLOR` environment variables are still followed on a
+            // terminal.
+            Self::Auto if !atty::is(atty::Stream::Stdout) => colored::control::set_override(false),
+            Self::Auto => {}
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(choice: &str) -> Result<Self, Self::Err> {
+        match choice {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!("unknown color choice `{}`", choice)),
+        }
+    }
 }
 
 /// Program entry point.
 fn main() {
+    CLI.color().apply();
+
     if let Some(path) = CLI.output() {
         if path.exists() {
             if !path.is_dir() {
@@ -104,36 +406,127 @@ fn main() {
         }
     }
 
+    // Zero threads lets rayon pick the number of threads.
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(CLI.threads().unwrap_or(0))
+        .stack_size(TEST_STACK_SIZE)
+        .build_global()
+        .expect("could not set up the thread pool");
+
     if CLI.verbose() {
         println!("Loading the test suite...");
     }
     let harness = read_harness().expect("could not read initialization bindings");
 
-    let global_suite = read_global_suite().expect("could not get the list of tests to run");
+    if let Some(Command::Run { path }) = CLI.command() {
+        let test = read_test(path).expect("could not read the test");
+        if !run_single(&test, &harness) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Err(e) = check_ignore_file() {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+
+    let mut global_suite = read_global_suite().expect("could not get the list of tests to run");
+
+    if CLI.list_ignored() {
+        list_ignored(&global_suite);
+        return;
+    }
+
+    if let Some(seed) = CLI.shuffle() {
+        println!("Shuffling the tests with seed {}", seed);
+        global_suite.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
 
     if CLI.verbose() {
         println!("Test suite loaded, starting tests...");
     }
-    let results = global_suite.run(&harness);
-    println!();
+    let style = if CLI.quiet() {
+        ProgressStyle::Quiet
+    } else if CLI.progress() && atty::is(atty::Stream::Stdout) {
+        ProgressStyle::Live
+    } else {
+        ProgressStyle::Dots
+    };
+    let progress = Progress::new(global_suite.count_tests(), style, CLI.max_panics());
+    let stream = match CLI.output() {
+        Some(path) if CLI.output_format() == OutputFormat::Ndjson => {
+            Some(SuiteStream::open(path).expect("could not open the suite results output file"))
+        }
+        _ => None,
+    };
+    let results = global_suite.run(&harness, &progress, stream.as_ref());
+    if style != ProgressStyle::Quiet {
+        println!();
+    }
 
     println!("Results:");
     println!("Total tests: {}", results.total);
     println!("Passed tests: {}", results.passed);
-    println!(
-        "Conformance: {:.2}%",
-        (results.passed as f64 / results.total as f64) * 100.0
-    );
+    println!("Failed tests: {}", results.failed);
+    println!("  Parse failures: {}", results.parse_failed);
+    println!("  Runtime failures: {}", results.runtime_failed);
+    println!("Panicked tests: {}", results.panic);
+    println!("Conformance: {:.2}%", results.conformance());
+    if CLI.verbose() {
+        println!(
+            "Elapsed time: {:.2}s",
+            Duration::from_millis(results.duration_ms).as_secs_f64()
+        );
+    }
+    print_slowest_tests(&results);
+    if CLI.leaked_globals() {
+        print_leaked_globals(&results);
+    }
+    if CLI.update_ignore() {
+        update_ignore(&global_suite, &results).expect("could not update test_ignore.txt");
+    }
+
+    if let Some(path) = CLI.compare() {
+        let comparison = compare(path, &results).expect("could not compare with the old results");
+        print_comparison(&comparison);
+
+        if let Some(output) = CLI.compare_output() {
+            write_comparison(output, &comparison).expect("could not write the comparison");
+        }
+    }
 
+    write_markdown(&results).expect("could not write the Markdown summary");
+    write_html(&results).expect("could not write the HTML report");
+    write_features(&results).expect("could not write the feature statistics");
+    let check = check_results(&results, CLI.fail_on_panic(), CLI.min_conformance());
     write_json(results).expect("could not write the results to the output JSON file");
+
+    // The partial results are written first, to be able to look at the panics.
+    if progress.aborted() {
+        eprintln!(
+            "the run was aborted after more than {} panics",
+            CLI.max_panics().unwrap_or_default()
+        );
+        std::process::exit(1);
+    }
+
+    if let Err(error) = check {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
 }
 
 /// All the harness include files.
+///
+/// It's cheap to clone, so that it can be sent to the threads running the tests.
 #[derive(Debug, Clone)]
 struct Harness {
-    assert: Box<str>,
-    sta: Box<str>,
-    includes: FxHashMap<Box<str>, Box<str>>,
+    assert: Arc<str>,
+    sta: Arc<str>,
+    includes: Arc<FxHashMap<Box<str>, Box<str>>>,
+    /// Whether `assert` and its methods are wrapped to count the assertions of the tests.
+    count_assertions: bool,
 }
 
 /// Represents a test suite.
@@ -144,6 +537,39 @@ struct TestSuite {
     tests: Box<[Test]>,
 }
 
+impl TestSuite {
+    /// Creates a new test suite.
+    ///
+    /// Sub-suites and tests are sorted by name, so that they always run in the same order, no
+    /// matter the order in which they were read from the file system.
+    fn new<N>(name: N, mut suites: Vec<TestSuite>, mut tests: Vec<Test>) -> Self
+    where
+        N: Into<Box<str>>,
+    {
+        suites.sort_by(|a, b| a.name.cmp(&b.name));
+        tests.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            name: name.into(),
+            suites: suites.into_boxed_slice(),
+            tests: tests.into_boxed_slice(),
+        }
+    }
+
+    /// Shuffles the tests of this suite and of all its sub-suites.
+    ///
+    /// The sub-suites themselves keep their order.
+    fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng,
+    {
+        for suite in self.suites.iter_mut() {
+            suite.shuffle(rng);
+        }
+        self.tests.shuffle(rng);
+    }
+}
+
 /// Outcome of a test suite.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SuiteResult {
@@ -155,11 +581,26 @@ struct SuiteResult {
     passed: usize,
     #[serde(rename = "i")]
     ignored: usize,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(rename = "f", default)]
+    failed: usize,
+    #[serde(rename = "pn", default)]
+    panic: usize,
+    #[serde(rename = "to", default)]
+    timed_out: usize,
+    /// Failed tests whose code could not be parsed.
+    #[serde(rename = "pf", default)]
+    parse_failed: usize,
+    /// Failed tests whose code was parsed, but failed when it ran, or whose harness failed.
+    #[serde(rename = "rf", default)]
+    runtime_failed: usize,
+    /// Wall-clock time spent running the suite, including its sub-suites, in milliseconds.
+    #[serde(rename = "d", default)]
+    duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     #[serde(rename = "s")]
     suites: Vec<SuiteResult>,
     #[serde(rename = "t")]
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     tests: Vec<TestResult>,
 }
 
@@ -168,8 +609,34 @@ struct SuiteResult {
 struct TestResult {
     #[serde(rename = "n")]
     name: Box<str>,
+    /// Path of the test file, relative to the `test` folder and separated by `/`.
+    #[serde(rename = "p", default)]
+    path: Box<str>,
     #[serde(rename = "r")]
     result: TestOutcomeResult,
+    #[serde(rename = "t", default, skip_serializing_if = "str::is_empty")]
+    result_text: Box<str>,
+    /// Number of assertions made by the test, with `--count-assertions`.
+    #[serde(rename = "a", default, skip_serializing_if = "is_zero")]
+    assertions: usize,
+    /// Time spent evaluating the test, in milliseconds, only used for the slowest tests summary.
+    #[serde(skip)]
+    duration_ms: u64,
+    /// Features of the test, only used for the feature statistics.
+    #[serde(skip)]
+    features: Box<[Box<str>]>,
+    /// Properties the test added to the global object, with `--leaked-globals`.
+    #[serde(rename = "g", default, skip_serializing_if = "<[_]>::is_empty")]
+    leaked_globals: Box<[Box<str>]>,
+    /// Whether the code of the test could not be parsed, only used for the failure breakdown.
+    #[serde(skip)]
+    parse_failed: bool,
+}
+
+/// Checks if a count is zero, to leave it out of the JSON results.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(count: &usize) -> bool {
+    *count == 0
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -182,12 +649,25 @@ enum TestOutcomeResult {
     Failed,
     #[serde(rename = "P")]
     Panic,
+    #[serde(rename = "T")]
+    Timeout,
+    /// The test passed in one of strict and non-strict mode, but failed in the other.
+    #[serde(rename = "M")]
+    MixedStrictNonStrict,
+    /// The environment of the test could not be set up, because of a broken harness file.
+    #[serde(rename = "H")]
+    HarnessError,
+    /// The test didn't match the `--filter` option, so it never ends up in the results.
+    #[serde(skip)]
+    Filtered,
 }
 
 /// Represents a test.
 #[derive(Debug, Clone)]
 struct Test {
     name: Box<str>,
+    /// Path of the test file, relative to the `test` folder and separated by `/`.
+    path: Box<str>,
     description: Box<str>,
     esid: Option<Box<str>>,
     flags: TestFlags,
@@ -202,13 +682,15 @@ struct Test {
 impl Test {
     /// Creates a new test.
     #[inline]
-    fn new<N, C>(name: N, content: C, metadata: MetaData) -> Self
+    fn new<N, P, C>(name: N, path: P, content: C, metadata: MetaData) -> Self
     where
         N: Into<Box<str>>,
+        P: Into<Box<str>>,
         C: Into<Box<str>>,
     {
         Self {
             name: name.into(),
+            path: path.into(),
             description: metadata.description,
             esid: metadata.esid,
             flags: metadata.flags.into(),
@@ -220,6 +702,24 @@ impl Test {
             content: content.into(),
         }
     }
+
+    /// Restricts the test to strict or non-strict mode.
+    ///
+    /// If the test can't run in that mode, it doesn't run in any.
+    #[inline]
+    fn restrict_to_mode(&mut self, strict: bool) {
+        self.flags.remove(if strict {
+            TestFlags::NO_STRICT
+        } else {
+            TestFlags::STRICT
+        });
+    }
+
+    /// Whether the test runs in strict mode, non-strict mode, or both.
+    #[inline]
+    fn runs_in_any_mode(&self) -> bool {
+        self.flags.intersects(TestFlags::default())
+    }
 }
 
 /// An outcome for a test.
@@ -296,7 +796,15 @@ where
             }
 
             if !result.intersects(Self::default()) {
-                result |= Self::default()
+                result |= if result.contains(Self::RAW) {
+                    // Raw tests run unmodified, so without a `"use strict"` directive.
+                    Self::NO_STRICT
+                } else if result.contains(Self::MODULE) {
+                    // Module code is always strict.
+                    Self::STRICT
+                } else {
+                    Self::default()
+                };
             }
 
             result
@@ -2,30 +2,42 @@
 
 use super::{Harness, Locale, Phase, Test, TestSuite, CLI};
 use fxhash::FxHashMap;
-use serde::Deserialize;
-use std::{fs, io, path::Path};
+use serde::{Deserialize, Deserializer};
+use std::{fs, io, path::Path, sync::Arc};
 
 /// Representation of the YAML metadata in Test262 tests.
+///
+/// Missing keys and keys without a value, like `features:`, get their default value.
 #[derive(Debug, Clone, Deserialize)]
 pub(super) struct MetaData {
+    #[serde(default, deserialize_with = "nullable")]
     pub(super) description: Box<str>,
     pub(super) esid: Option<Box<str>>,
     pub(super) es5id: Option<Box<str>>,
     pub(super) es6id: Option<Box<str>>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "nullable")]
     pub(super) info: Box<str>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "nullable")]
     pub(super) features: Box<[Box<str>]>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "nullable")]
     pub(super) includes: Box<[Box<str>]>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "nullable")]
     pub(super) flags: Box<[TestFlag]>,
     #[serde(default)]
     pub(super) negative: Option<Negative>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "nullable")]
     pub(super) locale: Locale,
 }
 
+/// Deserializes a value that can be `null`, which gives its default value.
+fn nullable<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// Negative test information structure.
 #[derive(Debug, Clone, Deserialize)]
 pub(super) struct Negative {
@@ -54,9 +66,31 @@ pub(super) enum TestFlag {
 
 /// Reads the Test262 defined bindings.
 pub(super) fn read_harness() -> io::Result<Harness> {
+    let mut harness = read_harness_dir(&CLI.harness_dir())?;
+    harness.count_assertions = CLI.count_assertions();
+    Ok(harness)
+}
+
+/// Reads the harness files in the given folder.
+///
+/// `assert.js` and `sta.js` are required, every other file is an include file.
+pub(super) fn read_harness_dir(path: &Path) -> io::Result<Harness> {
+    for required in ["assert.js", "sta.js"].iter() {
+        if !path.join(required).is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "the harness folder {} has no {} file",
+                    path.display(),
+                    required
+                ),
+            ));
+        }
+    }
+
     let mut includes = FxHashMap::default();
 
-    for entry in fs::read_dir(CLI.test262_path().join("harness"))? {
+    for entry in fs::read_dir(path)? {
         let entry = entry?;
         let file_name = entry.file_name();
         let file_name = file_name.to_string_lossy();
@@ -72,13 +106,14 @@ pub(super) fn read_harness() -> io::Result<Harness> {
             content.into_boxed_str(),
         );
     }
-    let assert = fs::read_to_string(CLI.test262_path().join("harness/assert.js"))?.into_boxed_str();
-    let sta = fs::read_to_string(CLI.test262_path().join("harness/sta.js"))?.into_boxed_str();
+    let assert = fs::read_to_string(path.join("assert.js"))?.into();
+    let sta = fs::read_to_string(path.join("sta.js"))?.into();
 
     Ok(Harness {
         assert,
         sta,
-        includes,
+        includes: Arc::new(includes),
+        count_assertions: false,
     })
 }
 
@@ -131,15 +166,11 @@ fn read_suite(path: &Path) -> io::Result<TestSuite> {
         }
     }
 
-    Ok(TestSuite {
-        name: name.into(),
-        suites: suites.into_boxed_slice(),
-        tests: tests.into_boxed_slice(),
-    })
+    Ok(TestSuite::new(name, suites, tests))
 }
 
 /// Reads information about a given test case.
-fn read_test(path: &Path) -> io::Result<Test> {
+pub(super) fn read_test(path: &Path) -> io::Result<Test> {
     let name = path
         .file_stem()
         .ok_or_else(|| {
@@ -156,10 +187,23 @@ fn read_test(path: &Path) -> io::Result<Test> {
             )
         })?;
 
+    let relative_path = path
+        .strip_prefix(CLI.test262_path().join("test"))
+        .unwrap_or(path)
+        .iter()
+        .map(|component| component.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
     let content = fs::read_to_string(path)?;
     let metadata = read_metadata(&content)?;
 
-    Ok(Test::new(name, content, metadata))
+    let mut test = Test::new(name, relative_path, content, metadata);
+    if let Some(strict) = CLI.strict_mode() {
+        test.restrict_to_mode(strict);
+    }
+
+    Ok(test)
 }
 
 /// Reads the metadata from the input test code.
@@ -167,9 +211,10 @@ fn read_metadata(code: &str) -> io::Result<MetaData> {
     use once_cell::sync::Lazy;
     use regex::Regex;
 
-    /// Regular expression to retrieve the metadata of a test.
+    /// Regular expression to retrieve the metadata of a test, up to the first `---*/`, since
+    /// the code after it may contain one too.
     static META_REGEX: Lazy<Regex> = Lazy::new(|| {
-        Regex::new(r#"/\*\-{3}((?:.|\n)*)\-{3}\*/"#)
+        Regex::new(r#"(?s)/\*-{3}(.*?)-{3}\*/"#)
             .expect("could not compile metadata regular expression")
     });
 
@@ -182,3 +227,134 @@ fn read_metadata(code: &str) -> io::Result<MetaData> {
 
     serde_yaml::from_str(yaml).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Outcome, TestFlags};
+
+    /// Gets the strings of a list from the metadata.
+    fn strs(list: &[Box<str>]) -> Vec<&str> {
+        list.iter().map(AsRef::as_ref).collect()
+    }
+
+    #[test]
+    fn metadata_with_multi_line_info() {
+        let metadata = read_metadata(
+            r#"// Copyright (C) 2017 the V8 project authors. All rights reserved.
+// This code is governed by the BSD license found in the LICENSE file.
+/*---
+esid: sec-array.prototype.flat
+description: >
+  Array.prototype.flat throws
+  on a revoked proxy
+info: |
+  1. Let O be ? ToObject(this value).
+
+  2. Let sourceLen be ? ToLength(? Get(O, "length")).
+includes: [propertyHelper.js, compareArray.js]
+features: [Array.prototype.flat, "Proxy"]
+flags: [onlyStrict]
+---*/
+
+assert.sameValue(typeof Array.prototype.flat, "function");
+"#,
+        )
+        .expect("could not read the metadata");
+
+        assert_eq!(
+            &*metadata.description,
+            "Array.prototype.flat throws on a revoked proxy\n"
+        );
+        assert_eq!(metadata.esid.as_deref(), Some("sec-array.prototype.flat"));
+        assert!(metadata
+            .info
+            .starts_with("1. Let O be ? ToObject(this value).\n\n2."));
+        assert_eq!(
+            strs(&metadata.includes),
+            ["propertyHelper.js", "compareArray.js"]
+        );
+        assert_eq!(strs(&metadata.features), ["Array.prototype.flat", "Proxy"]);
+        assert_eq!(TestFlags::from(metadata.flags), TestFlags::STRICT);
+        assert!(matches!(
+            Outcome::from(metadata.negative),
+            Outcome::Positive
+        ));
+    }
+
+    #[test]
+    fn metadata_of_raw_negative_test() {
+        let metadata = read_metadata(
+            "#!\n/*---\r\nesid: pending\r\ndescription: Hashbang comments should only be allowed at the start of source texts\r\nflags: [raw]\r\nnegative:\r\n  phase: parse\r\n  type: SyntaxError\r\nfeatures: [hashbang]\r\n---*/\r\n\r\n#!\r\n",
+        )
+        .expect("could not read the metadata");
+
+        assert_eq!(
+            TestFlags::from(metadata.flags),
+            TestFlags::RAW | TestFlags::NO_STRICT
+        );
+        assert!(matches!(
+            Outcome::from(metadata.negative),
+            Outcome::Negative {
+                phase: Phase::Parse,
+                ref error_type,
+            } if &**error_type == "SyntaxError"
+        ));
+    }
+
+    #[test]
+    fn metadata_with_multi_line_flow_sequences() {
+        let metadata = read_metadata(
+            r#"/*---
+es6id: 25.4.4.1
+description: Dynamic import in an asynchronous module
+features: [
+  dynamic-import,
+  "top-level-await"
+]
+flags: [module, async, generated]
+includes:
+locale: [en-US, de]
+---*/
+
+// The code may contain the end of the metadata: ---*/
+import('./module.js').then($DONE, $DONE);
+"#,
+        )
+        .expect("could not read the metadata");
+
+        assert_eq!(metadata.es6id.as_deref(), Some("25.4.4.1"));
+        assert_eq!(
+            strs(&metadata.features),
+            ["dynamic-import", "top-level-await"]
+        );
+        assert!(metadata.includes.is_empty());
+        assert_eq!(strs(&metadata.locale.locale), ["en-US", "de"]);
+        assert_eq!(
+            TestFlags::from(metadata.flags),
+            TestFlags::MODULE | TestFlags::ASYNC | TestFlags::GENERATED | TestFlags::STRICT
+        );
+    }
+
+    #[test]
+    fn metadata_without_flags() {
+        let metadata = read_metadata(
+            "/*---\ndescription: Both modes\nflags:\nnegative:\n  phase: runtime\n  type: Test262Error\n---*/\nthrow new Test262Error();\n",
+        )
+        .expect("could not read the metadata");
+
+        assert_eq!(TestFlags::from(metadata.flags), TestFlags::default());
+        assert!(matches!(
+            Outcome::from(metadata.negative),
+            Outcome::Negative {
+                phase: Phase::Runtime,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn metadata_is_required() {
+        assert!(read_metadata("assert(true);\n").is_err());
+    }
+}
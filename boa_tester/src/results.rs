@@ -1,16 +1,36 @@
-use super::{SuiteResult, CLI};
+use super::{SuiteResult, TestOutcomeResult, TestResult, CLI};
+use colored::Colorize;
+use fxhash::{FxHashMap, FxHashSet};
 use git2::Repository;
 use hex::ToHex;
 use serde::{Deserialize, Serialize};
 use std::{
-    env, fs,
-    io::{self, BufReader, BufWriter},
-    path::Path,
+    cmp::Ordering,
+    collections::BTreeMap,
+    env,
+    fmt::Write,
+    fs,
+    io::{self, BufReader, BufWriter, Write as _},
+    iter,
+    path::{Path, PathBuf},
+    slice,
+    str::FromStr,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
 };
 
+/// Version of the format of the JSON results.
+///
+/// It must be bumped whenever the format changes in a way that files written before can't be read
+/// anymore. Files written before the version was added don't have it, and are version 0.
+const RESULTS_VERSION: u32 = 1;
+
 /// Structure to store full result information.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct ResultInfo {
+    #[serde(rename = "v", default)]
+    version: u32,
     #[serde(rename = "c")]
     commit: Box<str>,
     #[serde(rename = "u")]
@@ -19,9 +39,11 @@ struct ResultInfo {
     results: SuiteResult,
 }
 
-/// Structure to store full result information.
+/// Structure to store reduced result information.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct ReducedResultInfo {
+    #[serde(rename = "v", default)]
+    version: u32,
     #[serde(rename = "c")]
     commit: Box<str>,
     #[serde(rename = "u")]
@@ -32,17 +54,27 @@ struct ReducedResultInfo {
     passed: usize,
     #[serde(rename = "i")]
     ignored: usize,
+    #[serde(rename = "f", default)]
+    failed: usize,
+    #[serde(rename = "pn", default)]
+    panic: usize,
+    #[serde(rename = "s", default, skip_serializing_if = "Option::is_none")]
+    suite: Option<Box<str>>,
 }
 
 impl From<ResultInfo> for ReducedResultInfo {
     /// Creates a new reduced suite result from a full suite result.
     fn from(info: ResultInfo) -> Self {
         Self {
+            version: info.version,
             commit: info.commit,
             test262_commit: info.test262_commit,
             total: info.results.total,
             passed: info.results.passed,
             ignored: info.results.ignored,
+            failed: info.results.failed,
+            panic: info.results.panic,
+            suite: None,
         }
     }
 }
@@ -53,23 +85,26 @@ const LATEST_FILE_NAME: &str = "latest.json";
 /// File name of the "all results" JSON file.
 const RESULTS_FILE_NAME: &str = "results.json";
 
+/// File name of the results of each suite, in the `ndjson` output format.
+const SUITE_RESULTS_FILE_NAME: &str = "results.ndjson";
+
+/// File name of the Markdown summary.
+const SUMMARY_FILE_NAME: &str = "summary.md";
+
+/// File name of the feature statistics JSON file.
+const FEATURES_FILE_NAME: &str = "features.json";
+
+/// File name of the HTML report.
+const REPORT_FILE_NAME: &str = "report.html";
+
 /// Writes the results of running the test suite to the given JSON output file.
 ///
-/// It will append the results to the ones already present, in an array.
+/// It will append the results to the ones already present, in an array. In the `ndjson` output
+/// format, the results of the suites were already streamed, so only the latest results are
+/// written.
 pub(crate) fn write_json(results: SuiteResult) -> io::Result<()> {
     if let Some(path) = CLI.output() {
-        let mut branch = env::var("GITHUB_REF").unwrap_or_default();
-        if branch.starts_with("refs/pull") {
-            branch = "pull".to_owned();
-        }
-
-        let path = if branch.is_empty() {
-            path.to_path_buf()
-        } else {
-            let folder = path.join(branch);
-            fs::create_dir_all(&folder)?;
-            folder
-        };
+        let path = output_folder(path)?;
 
         if CLI.verbose() {
             println!("Writing the results to {}...", path.display());
@@ -80,31 +115,491 @@ pub(crate) fn write_json(results: SuiteResult) -> io::Result<()> {
         let latest_path = path.join(LATEST_FILE_NAME);
 
         let new_results = ResultInfo {
+            version: RESULTS_VERSION,
             commit: env::var("GITHUB_SHA").unwrap_or_default().into_boxed_str(),
             test262_commit: get_test262_commit(),
             results,
         };
 
-        let latest_output = BufWriter::new(fs::File::create(latest_path)?);
-        serde_json::to_writer(latest_output, &new_results)?;
+        write_json_atomically(&latest_path, &new_results)?;
 
-        // Write the full list of results, retrieving the existing ones first.
+        if CLI.output_format() == OutputFormat::Ndjson {
+            return Ok(());
+        }
+
+        // Write the full list of results, retrieving the existing ones first. The lock keeps
+        // concurrent runs from losing each other's results.
 
         let all_path = path.join(RESULTS_FILE_NAME);
+        let _lock = OutputLock::acquire(&all_path)?;
+        append_result(&all_path, new_results.into())?;
 
-        let mut all_results: Vec<ReducedResultInfo> = if all_path.exists() {
-            serde_json::from_reader(BufReader::new(fs::File::open(&all_path)?))?
-        } else {
-            Vec::new()
+        if CLI.verbose() {
+            println!("Results written correctly");
+        }
+    }
+
+    Ok(())
+}
+
+/// Appends a result to the list of results stored in the given JSON file, creating it if needed.
+fn append_result(path: &Path, result: ReducedResultInfo) -> io::Result<()> {
+    let mut all_results: Vec<ReducedResultInfo> = if path.exists() {
+        serde_json::from_reader(BufReader::new(fs::File::open(path)?))?
+    } else {
+        Vec::new()
+    };
+
+    all_results.push(result);
+
+    write_json_atomically(path, &all_results)
+}
+
+/// Writes a value to the given JSON file, so that the file is never left half written.
+///
+/// The value is written to a temporary file first, which then replaces the file.
+fn write_json_atomically<T>(path: &Path, value: &T) -> io::Result<()>
+where
+    T: Serialize,
+{
+    let temp_path = write_json_temp(path, value)?;
+    fs::rename(temp_path, path)
+}
+
+/// Writes a value to the temporary file next to the given JSON file, returning its path.
+fn write_json_temp<T>(path: &Path, value: &T) -> io::Result<PathBuf>
+where
+    T: Serialize,
+{
+    let temp_path = path.with_extension("json.tmp");
+
+    let file = fs::File::create(&temp_path)?;
+    let mut output = BufWriter::new(&file);
+    serde_json::to_writer(&mut output, value)?;
+    output.flush()?;
+    drop(output);
+    file.sync_all()?;
+
+    Ok(temp_path)
+}
+
+/// Format of the results of the runs kept in the output folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutputFormat {
+    /// All the runs in a `results.json` array, rewritten at the end of each run.
+    Json,
+    /// One line per suite appended to `results.ndjson`, as soon as the suite finishes.
+    Ndjson,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(format!("unknown output format `{}`", format)),
+        }
+    }
+}
+
+/// Output file the results of each suite are streamed to, in the `ndjson` output format.
+///
+/// Each line is the reduced result of a suite, written and flushed as soon as the suite finishes,
+/// so that a run that is killed keeps the results of the suites that finished.
+#[derive(Debug)]
+pub(crate) struct SuiteStream {
+    output: Mutex<BufWriter<fs::File>>,
+    commit: Box<str>,
+    test262_commit: Box<str>,
+}
+
+impl SuiteStream {
+    /// Opens the suite results file of the given output folder, appending to it.
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        let path = output_folder(path)?.join(SUITE_RESULTS_FILE_NAME);
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        Ok(Self {
+            output: Mutex::new(BufWriter::new(file)),
+            commit: env::var("GITHUB_SHA").unwrap_or_default().into_boxed_str(),
+            test262_commit: get_test262_commit(),
+        })
+    }
+
+    /// Writes the reduced result of the suite at the given path as a line.
+    pub(crate) fn write(&self, path: &str, result: &SuiteResult) -> io::Result<()> {
+        let info = ReducedResultInfo {
+            version: RESULTS_VERSION,
+            commit: self.commit.clone(),
+            test262_commit: self.test262_commit.clone(),
+            total: result.total,
+            passed: result.passed,
+            ignored: result.ignored,
+            failed: result.failed,
+            panic: result.panic,
+            suite: Some(path.into()),
         };
+        let mut line = serde_json::to_vec(&info)?;
+        line.push(b'\n');
+
+        let mut output = self.output.lock().expect("suite results output poisoned");
+        output.write_all(&line)?;
+        output.flush()
+    }
+}
 
-        all_results.push(new_results.into());
+/// Lock file that keeps concurrent runs from writing to the same results file.
+///
+/// It is removed when dropped.
+#[derive(Debug)]
+struct OutputLock {
+    path: PathBuf,
+}
 
-        let output = BufWriter::new(fs::File::create(&all_path)?);
-        serde_json::to_writer(output, &all_results)?;
+impl OutputLock {
+    /// Maximum time to wait for another run to release the lock.
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Creates the lock file of the given results file, waiting for any other run holding it.
+    fn acquire(path: &Path) -> io::Result<Self> {
+        let path = path.with_extension("json.lock");
+        let start = Instant::now();
+
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(_) => return Ok(Self { path }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    if start.elapsed() > Self::TIMEOUT {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!(
+                                "{} is still locked, remove it if no other run is writing the \
+                                 results",
+                                path.display()
+                            ),
+                        ));
+                    }
+                    thread::sleep(Duration::from_millis(100));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for OutputLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Writes a Markdown summary of the results next to the JSON output files, if requested.
+pub(crate) fn write_markdown(results: &SuiteResult) -> io::Result<()> {
+    if let (true, Some(path)) = (CLI.markdown(), CLI.output()) {
+        let path = output_folder(path)?.join(SUMMARY_FILE_NAME);
 
         if CLI.verbose() {
-            println!("Results written correctly");
+            println!("Writing the Markdown summary to {}...", path.display());
+        }
+
+        fs::write(path, render_markdown(results))?;
+    }
+
+    Ok(())
+}
+
+/// Writes an HTML report of the results next to the JSON output files, if requested.
+pub(crate) fn write_html(results: &SuiteResult) -> io::Result<()> {
+    if let (true, Some(path)) = (CLI.html(), CLI.output()) {
+        let path = output_folder(path)?.join(REPORT_FILE_NAME);
+
+        if CLI.verbose() {
+            println!("Writing the HTML report to {}...", path.display());
+        }
+
+        fs::write(path, render_html(results))?;
+    }
+
+    Ok(())
+}
+
+/// Writes the statistics of each test262 feature next to the JSON output files.
+pub(crate) fn write_features(results: &SuiteResult) -> io::Result<()> {
+    if let Some(path) = CLI.output() {
+        let path = output_folder(path)?.join(FEATURES_FILE_NAME);
+
+        if CLI.verbose() {
+            println!("Writing the feature statistics to {}...", path.display());
+        }
+
+        let output = BufWriter::new(fs::File::create(path)?);
+        serde_json::to_writer(output, &feature_stats(results))?;
+    }
+
+    Ok(())
+}
+
+/// Number of tests of a test262 feature, and how many of them passed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+struct FeatureResult {
+    #[serde(rename = "c")]
+    total: usize,
+    #[serde(rename = "p")]
+    passed: usize,
+}
+
+/// Counts the tests of each test262 feature, as listed in the `features` metadata of the tests.
+fn feature_stats(results: &SuiteResult) -> BTreeMap<Box<str>, FeatureResult> {
+    let mut stats = BTreeMap::<_, FeatureResult>::new();
+    for (_, test) in results.flat_tests() {
+        for feature in test.features.iter() {
+            let stat = stats.entry(feature.clone()).or_default();
+            stat.total += 1;
+            if test.result == TestOutcomeResult::Passed {
+                stat.passed += 1;
+            }
+        }
+    }
+
+    stats
+}
+
+impl SuiteResult {
+    /// Percentage of passed tests in the suite, which is 0 if the suite has no test.
+    pub(crate) fn conformance(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.passed as f64 / self.total as f64) * 100.0
+        }
+    }
+
+    /// Iterates over the results of all the tests of the suite and its sub-suites, depth first,
+    /// along with the path of each test file relative to the suite.
+    ///
+    /// The paths are built from the suite and test names, so they are also known for results
+    /// written before the paths of the tests were stored.
+    pub(crate) fn flat_tests(&self) -> impl Iterator<Item = (PathBuf, &TestResult)> {
+        let mut suites = vec![(PathBuf::new(), self)];
+        let mut current: Option<(PathBuf, slice::Iter<'_, TestResult>)> = None;
+
+        iter::from_fn(move || loop {
+            if let Some((path, tests)) = &mut current {
+                if let Some(test) = tests.next() {
+                    return Some((path.join(format!("{}.js", test.name)), test));
+                }
+            }
+
+            let (path, suite) = suites.pop()?;
+            // The sub-suites are pushed in reverse, so that they are walked in order.
+            suites.extend(
+                suite
+                    .suites
+                    .iter()
+                    .rev()
+                    .map(|sub_suite| (path.join(&*sub_suite.name), sub_suite)),
+            );
+            current = Some((path, suite.tests.iter()));
+        })
+    }
+}
+
+/// Gets the folder where the results must be written, creating it if needed.
+///
+/// Results of each branch go in a separate sub-folder of the output folder.
+fn output_folder(path: &Path) -> io::Result<PathBuf> {
+    let mut branch = env::var("GITHUB_REF").unwrap_or_default();
+    if branch.starts_with("refs/pull") {
+        branch = "pull".to_owned();
+    }
+
+    if branch.is_empty() {
+        Ok(path.to_path_buf())
+    } else {
+        let folder = path.join(branch);
+        fs::create_dir_all(&folder)?;
+        Ok(folder)
+    }
+}
+
+/// Renders the Markdown summary of the results.
+///
+/// It contains the global counts, and the top-level suites sorted by pass rate.
+fn render_markdown(results: &SuiteResult) -> String {
+    let mut suites: Vec<_> = results.suites.iter().collect();
+    suites.sort_by(|a, b| {
+        b.conformance()
+            .partial_cmp(&a.conformance())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut markdown = String::new();
+    let _ = writeln!(markdown, "# Test262 conformance results\n");
+    let _ = writeln!(
+        markdown,
+        "| Total | Passed | Ignored | Panics | Conformance |\n\
+         | ----: | -----: | ------: | -----: | ----------: |"
+    );
+    let _ = writeln!(
+        markdown,
+        "| {} | {} | {} | {} | {:.2}% |",
+        results.total,
+        results.passed,
+        results.ignored,
+        results.panic,
+        results.conformance()
+    );
+
+    let _ = writeln!(
+        markdown,
+        "\n## Suites\n\n\
+         | Suite | Total | Passed | Ignored | Conformance |\n\
+         | :---- | ----: | -----: | ------: | ----------: |"
+    );
+    for suite in suites {
+        let _ = writeln!(
+            markdown,
+            "| {} | {} | {} | {} | {:.2}% |",
+            suite.name,
+            suite.total,
+            suite.passed,
+            suite.ignored,
+            suite.conformance()
+        );
+    }
+
+    markdown
+}
+
+/// Style sheet of the HTML report.
+const REPORT_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+.suite { margin-left: 1.5em; }
+.header { cursor: pointer; padding: 0.2em 0; }
+.header::before { content: '\\25BE'; display: inline-block; width: 1em; }
+.collapsed > .header::before { content: '\\25B8'; }
+.collapsed > .content { display: none; }
+.bar { display: inline-block; width: 10em; height: 0.8em; margin: 0 0.5em; background: #e55; }
+.bar > span { display: block; height: 100%; background: #4b4; }
+.test { margin-left: 1.5em; cursor: pointer; color: #c33; }
+.test > pre { display: none; color: black; background: #eee; padding: 0.5em; cursor: auto; }
+.test.open > pre { display: block; }";
+
+/// Script of the HTML report, toggling the suites and the details of the failing tests.
+const REPORT_SCRIPT: &str = "\
+for (const header of document.querySelectorAll('.header')) {
+  header.addEventListener('click', () => header.parentNode.classList.toggle('collapsed'));
+}
+for (const name of document.querySelectorAll('.test > span')) {
+  name.addEventListener('click', () => name.parentNode.classList.toggle('open'));
+}";
+
+/// Renders the HTML report of the results.
+///
+/// It is a self-contained page with the tree of suites, each with its pass bar and its failing
+/// tests, whose result text is shown when clicked.
+fn render_html(results: &SuiteResult) -> String {
+    let mut html = String::new();
+    let _ = writeln!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Test262 conformance results</title>\n<style>\n{}\n</style>\n</head>\n<body>\n\
+         <h1>Test262 conformance results</h1>",
+        REPORT_STYLE
+    );
+
+    render_html_suite(results, true, &mut html);
+
+    let _ = writeln!(
+        html,
+        "<script>\n{}\n</script>\n</body>\n</html>",
+        REPORT_SCRIPT
+    );
+    html
+}
+
+/// Renders a suite of the HTML report, with its sub-suites and failing tests.
+///
+/// Only the top-level suite is expanded initially.
+fn render_html_suite(suite: &SuiteResult, expanded: bool, html: &mut String) {
+    let conformance = suite.conformance();
+    let _ = writeln!(
+        html,
+        "<div class=\"suite{}\">\n<div class=\"header\"><b>{}</b>\
+         <span class=\"bar\"><span style=\"width: {:.2}%\"></span></span>\
+         {} / {} passed, {} ignored, {} failed, {} panics ({:.2}%)</div>\n<div class=\"content\">",
+        if expanded { "" } else { " collapsed" },
+        escape_html(&suite.name),
+        conformance,
+        suite.passed,
+        suite.total,
+        suite.ignored,
+        suite.failed,
+        suite.panic,
+        conformance
+    );
+
+    for sub_suite in &suite.suites {
+        render_html_suite(sub_suite, false, html);
+    }
+
+    for test in suite.tests.iter().filter(|test| is_failure(test.result)) {
+        let _ = writeln!(
+            html,
+            "<div class=\"test\"><span>{}</span><pre>{}</pre></div>",
+            escape_html(&test.name),
+            escape_html(&test.result_text)
+        );
+    }
+
+    let _ = writeln!(html, "</div>\n</div>");
+}
+
+/// Escapes the characters with a special meaning in HTML.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Checks whether the results are good enough for the tester to exit successfully.
+///
+/// It fails if `fail_on_panic` is set and any test panicked, or if the conformance is below
+/// `min_conformance`.
+pub(crate) fn check_results(
+    results: &SuiteResult,
+    fail_on_panic: bool,
+    min_conformance: Option<f64>,
+) -> Result<(), String> {
+    if fail_on_panic && results.panic != 0 {
+        return Err(format!("{} tests panicked", results.panic));
+    }
+
+    if let Some(min_conformance) = min_conformance {
+        let conformance = results.conformance();
+        if conformance < min_conformance {
+            return Err(format!(
+                "conformance of {:.2}% is below the minimum of {:.2}%",
+                conformance, min_conformance
+            ));
         }
     }
 
@@ -112,19 +607,692 @@ pub(crate) fn write_json(results: SuiteResult) -> io::Result<()> {
 }
 
 /// Gets the commit OID of the test262 submodule.
+///
+/// It is empty if the tester doesn't run from a checkout of the repository with the submodule,
+/// like the commit of Boa outside of the CI.
 fn get_test262_commit() -> Box<str> {
-    let repo = Repository::open(".").expect("could not open git repository in current directory");
-
-    let submodule = repo
-        .submodules()
-        .expect("could not get the list of submodules of the repo")
-        .into_iter()
-        .find(|sub| sub.path() == Path::new("test262"))
-        .expect("test262 submodule not found");
-
-    submodule
-        .index_id()
-        .expect("could not get the commit OID")
-        .encode_hex::<String>()
+    Repository::open(".")
+        .ok()
+        .and_then(|repo| {
+            repo.submodules()
+                .ok()?
+                .into_iter()
+                .find(|sub| sub.path() == Path::new("test262"))?
+                .index_id()
+        })
+        .map(|oid| oid.encode_hex::<String>())
+        .unwrap_or_default()
         .into_boxed_str()
 }
+
+/// Differences between an old run of the test suite and a new one.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct ResultsComparison {
+    /// Tests that passed (or were ignored) before and now fail or panic.
+    new_failures: Vec<ChangedTest>,
+    /// Tests that did not pass before and now pass.
+    new_fixes: Vec<ChangedTest>,
+}
+
+/// A test whose outcome changed between two runs of the test suite.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ChangedTest {
+    /// Path of the test, made of the names of its suites and of its own name.
+    path: Box<str>,
+    /// New outcome of the test.
+    result: TestOutcomeResult,
+    /// Reason why the test does not pass anymore, if any.
+    result_text: Box<str>,
+}
+
+/// Compares the results in the given `latest.json` file with the new results.
+pub(crate) fn compare(old_path: &Path, results: &SuiteResult) -> io::Result<ResultsComparison> {
+    let old_results = parse_result_info(&fs::read_to_string(old_path)?)?;
+
+    Ok(compare_results(&old_results.results, results))
+}
+
+/// Parses the full result information in a `latest.json` file, checking its version first.
+///
+/// The fields added since version 0 all have a default value, so older files can be read as they
+/// are.
+fn parse_result_info(json: &str) -> io::Result<ResultInfo> {
+    #[derive(Deserialize)]
+    struct Version {
+        #[serde(rename = "v", default)]
+        version: u32,
+    }
+
+    let Version { version } = serde_json::from_str(json)?;
+    if version > RESULTS_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "the results have version {}, but only versions up to {} are supported",
+                version, RESULTS_VERSION
+            ),
+        ));
+    }
+
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Compares two result trees, matching tests by their path in the tree, and storing the
+/// differences with their path, without the `.js` extension.
+fn compare_results(old: &SuiteResult, new: &SuiteResult) -> ResultsComparison {
+    let old_tests: FxHashMap<_, _> = old.flat_tests().collect();
+
+    let mut comparison = ResultsComparison::default();
+    for (path, new_test) in new.flat_tests() {
+        if let Some(old_test) = old_tests.get(&path) {
+            let was_passing = matches!(
+                old_test.result,
+                TestOutcomeResult::Passed | TestOutcomeResult::Ignored
+            );
+            let is_failing = is_failure(new_test.result);
+            let changed = || ChangedTest {
+                path: slash_path(&path.with_extension("")),
+                result: new_test.result,
+                result_text: new_test.result_text.clone(),
+            };
+
+            if was_passing && is_failing {
+                comparison.new_failures.push(changed());
+            } else if old_test.result != TestOutcomeResult::Passed
+                && new_test.result == TestOutcomeResult::Passed
+            {
+                comparison.new_fixes.push(changed());
+            }
+        }
+    }
+
+    comparison
+}
+
+/// Whether the outcome of a test is a failure, which includes panics and timeouts.
+fn is_failure(result: TestOutcomeResult) -> bool {
+    matches!(
+        result,
+        TestOutcomeResult::Failed
+            | TestOutcomeResult::Panic
+            | TestOutcomeResult::Timeout
+            | TestOutcomeResult::MixedStrictNonStrict
+            | TestOutcomeResult::HarnessError
+    )
+}
+
+/// Reads the paths of the tests that failed in the given `latest.json` file.
+pub(crate) fn read_failures(path: &Path) -> io::Result<FxHashSet<Box<str>>> {
+    let results = parse_result_info(&fs::read_to_string(path)?)?;
+
+    Ok(collect_failures(&results.results))
+}
+
+/// Collects the paths of the failed tests of a suite and its sub-suites.
+///
+/// The paths of results written before they were stored are rebuilt from the suite names.
+fn collect_failures(suite: &SuiteResult) -> FxHashSet<Box<str>> {
+    suite
+        .flat_tests()
+        .filter(|(_, test)| is_failure(test.result))
+        .map(|(path, test)| {
+            if test.path.is_empty() {
+                slash_path(&path)
+            } else {
+                test.path.clone()
+            }
+        })
+        .collect()
+}
+
+/// Joins the components of a path with `/`, the separator used in the results whatever the
+/// platform.
+fn slash_path(path: &Path) -> Box<str> {
+    path.iter()
+        .map(|component| component.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+        .into_boxed_str()
+}
+
+/// Writes the comparison of two runs of the test suite to the given JSON file.
+pub(crate) fn write_comparison(path: &Path, comparison: &ResultsComparison) -> io::Result<()> {
+    write_json_atomically(path, comparison)
+}
+
+/// Prints the comparison of two runs of the test suite.
+pub(crate) fn print_comparison(comparison: &ResultsComparison) {
+    println!();
+    println!("Comparison with the previous results:");
+
+    if !comparison.new_failures.is_empty() {
+        println!("{}", "New failures:".red());
+        for test in &comparison.new_failures {
+            println!("  {}", test.path.red());
+        }
+    }
+
+    if !comparison.new_fixes.is_empty() {
+        println!("{}", "New fixes:".green());
+        for test in &comparison.new_fixes {
+            println!("  {}", test.path.green());
+        }
+    }
+
+    let delta = comparison.new_fixes.len() as isize - comparison.new_failures.len() as isize;
+    println!(
+        "{} new failures, {} new fixes, net change: {:+} passing tests",
+        comparison.new_failures.len(),
+        comparison.new_fixes.len(),
+        delta
+    );
+}
+
+/// Gets the `count` tests that took the longest to run, the slowest first.
+///
+/// Tests that took the same time are sorted by path, so the list is always the same.
+fn slowest_tests(results: &SuiteResult, count: usize) -> Vec<&TestResult> {
+    let mut tests: Vec<_> = results.flat_tests().map(|(_, test)| test).collect();
+    tests.sort_by(|a, b| {
+        b.duration_ms
+            .cmp(&a.duration_ms)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    tests.truncate(count);
+    tests
+}
+
+/// Gets the tests that left new properties on the global object, sorted by path.
+fn leaking_tests(results: &SuiteResult) -> Vec<&TestResult> {
+    let mut tests: Vec<_> = results
+        .flat_tests()
+        .map(|(_, test)| test)
+        .filter(|test| !test.leaked_globals.is_empty())
+        .collect();
+    tests.sort_by(|a, b| a.path.cmp(&b.path));
+    tests
+}
+
+/// Prints the tests that left new properties on the global object, with the names of the
+/// properties.
+pub(crate) fn print_leaked_globals(results: &SuiteResult) {
+    println!();
+    println!("Leaked globals:");
+    for test in leaking_tests(results) {
+        println!("  {}: {}", test.path, test.leaked_globals.join(", "));
+    }
+}
+
+/// Prints the ten tests that took the longest to run.
+pub(crate) fn print_slowest_tests(results: &SuiteResult) {
+    println!();
+    println!("Slowest tests:");
+    for test in slowest_tests(results, 10) {
+        println!("  {} ms: {}", test.duration_ms, test.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suite(name: &str, suites: Vec<SuiteResult>, tests: Vec<TestResult>) -> SuiteResult {
+        SuiteResult {
+            name: name.into(),
+            total: 0,
+            passed: 0,
+            ignored: 0,
+            failed: 0,
+            panic: 0,
+            timed_out: 0,
+            parse_failed: 0,
+            runtime_failed: 0,
+            duration_ms: 0,
+            suites,
+            tests,
+        }
+    }
+
+    fn timed_test(path: &str, duration_ms: u64) -> TestResult {
+        TestResult {
+            path: path.into(),
+            duration_ms,
+            ..test(path, TestOutcomeResult::Passed)
+        }
+    }
+
+    fn test(name: &str, result: TestOutcomeResult) -> TestResult {
+        TestResult {
+            name: name.into(),
+            path: format!("{}.js", name).into_boxed_str(),
+            result,
+            result_text: Box::default(),
+            assertions: 0,
+            duration_ms: 0,
+            features: Box::default(),
+            leaked_globals: Box::default(),
+            parse_failed: false,
+        }
+    }
+
+    #[test]
+    fn compare_finds_new_failures_and_fixes() {
+        let old = suite(
+            "test",
+            vec![suite(
+                "built-ins",
+                vec![],
+                vec![
+                    test("broken", TestOutcomeResult::Passed),
+                    test("fixed", TestOutcomeResult::Failed),
+                    test("unchanged", TestOutcomeResult::Passed),
+                ],
+            )],
+            vec![],
+        );
+        let new = suite(
+            "test",
+            vec![suite(
+                "built-ins",
+                vec![],
+                vec![
+                    test("broken", TestOutcomeResult::Panic),
+                    test("fixed", TestOutcomeResult::Passed),
+                    test("unchanged", TestOutcomeResult::Passed),
+                    test("added", TestOutcomeResult::Failed),
+                ],
+            )],
+            vec![],
+        );
+
+        let comparison = compare_results(&old, &new);
+
+        let paths = |tests: &[ChangedTest]| -> Vec<Box<str>> {
+            tests.iter().map(|test| test.path.clone()).collect()
+        };
+        assert_eq!(paths(&comparison.new_failures), ["built-ins/broken".into()]);
+        assert_eq!(paths(&comparison.new_fixes), ["built-ins/fixed".into()]);
+    }
+
+    #[test]
+    fn check_results_fails_on_panics_and_low_conformance() {
+        let mut results = suite(
+            "test",
+            vec![],
+            vec![
+                test("passing", TestOutcomeResult::Passed),
+                test("panicking", TestOutcomeResult::Panic),
+            ],
+        );
+        results.total = 2;
+        results.passed = 1;
+        results.panic = 1;
+
+        assert!(check_results(&results, false, None).is_ok());
+        assert!(check_results(&results, true, None).is_err());
+        assert!(check_results(&results, false, Some(50.0)).is_ok());
+        assert!(check_results(&results, false, Some(75.0)).is_err());
+    }
+
+    #[test]
+    fn parse_result_info_versions() {
+        let legacy = r#"{"c":"abc","u":"def","r":{"n":"test","c":1,"p":1,"i":0,
+            "t":[{"n":"passing","r":"O"}]}}"#;
+        let legacy = parse_result_info(legacy).unwrap();
+        assert_eq!(legacy.version, 0);
+        assert_eq!(legacy.results.failed, 0);
+        assert_eq!(&*legacy.results.tests[0].path, "");
+
+        let current = ResultInfo {
+            version: RESULTS_VERSION,
+            commit: "abc".into(),
+            test262_commit: "def".into(),
+            results: suite(
+                "test",
+                vec![],
+                vec![test("failing", TestOutcomeResult::Failed)],
+            ),
+        };
+        let current = parse_result_info(&serde_json::to_string(&current).unwrap()).unwrap();
+        assert_eq!(current.version, RESULTS_VERSION);
+        assert_eq!(current.results.tests[0].result, TestOutcomeResult::Failed);
+
+        let future = format!(r#"{{"v":{},"c":"abc"}}"#, RESULTS_VERSION + 1);
+        let error = parse_result_info(&future).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn conformance_of_an_empty_suite() {
+        let results = suite("test", vec![], vec![]);
+        assert_eq!(format!("{:.2}%", results.conformance()), "0.00%");
+    }
+
+    #[test]
+    fn flat_tests_walk_the_whole_tree() {
+        let results = suite(
+            "test",
+            vec![
+                suite(
+                    "built-ins",
+                    vec![suite(
+                        "Array",
+                        vec![],
+                        vec![test("from", TestOutcomeResult::Passed)],
+                    )],
+                    vec![test("global", TestOutcomeResult::Failed)],
+                ),
+                suite("language", vec![suite("empty", vec![], vec![])], vec![]),
+            ],
+            vec![test("top", TestOutcomeResult::Passed)],
+        );
+
+        let tests: Vec<_> = results
+            .flat_tests()
+            .map(|(path, test)| (path, &*test.name))
+            .collect();
+        assert_eq!(
+            tests,
+            [
+                (PathBuf::from("top.js"), "top"),
+                (PathBuf::from("built-ins/global.js"), "global"),
+                (PathBuf::from("built-ins/Array/from.js"), "from"),
+            ]
+        );
+    }
+
+    #[test]
+    fn collect_failures_of_nested_suites() {
+        let mut failing = test("failing", TestOutcomeResult::Failed);
+        failing.path = "built-ins/Array/failing.js".into();
+        let mut legacy = test("legacy-failing", TestOutcomeResult::Panic);
+        legacy.path = Box::default();
+
+        let results = suite(
+            "test",
+            vec![suite(
+                "built-ins",
+                vec![suite(
+                    "Array",
+                    vec![],
+                    vec![failing, test("passing", TestOutcomeResult::Passed), legacy],
+                )],
+                vec![test("ignored", TestOutcomeResult::Ignored)],
+            )],
+            vec![],
+        );
+
+        let mut failures: Vec<_> = collect_failures(&results).into_iter().collect();
+        failures.sort();
+        assert_eq!(
+            failures,
+            [
+                "built-ins/Array/failing.js".into(),
+                "built-ins/Array/legacy-failing.js".into()
+            ]
+        );
+    }
+
+    #[test]
+    fn append_result_replaces_the_file_atomically() {
+        let dir = env::temp_dir().join(format!("boa_tester_append_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(RESULTS_FILE_NAME);
+        let read = || -> Vec<ReducedResultInfo> {
+            serde_json::from_reader(BufReader::new(fs::File::open(&path).unwrap())).unwrap()
+        };
+        let info = |commit: &str| ReducedResultInfo {
+            version: RESULTS_VERSION,
+            commit: commit.into(),
+            test262_commit: "abc".into(),
+            total: 2,
+            passed: 1,
+            ignored: 0,
+            failed: 1,
+            panic: 0,
+            suite: None,
+        };
+
+        append_result(&path, info("first")).unwrap();
+        append_result(&path, info("second")).unwrap();
+
+        // A run interrupted before the rename leaves the results file intact.
+        write_json_temp(&path, &"garbage").unwrap();
+        let commits: Vec<_> = read().into_iter().map(|info| info.commit).collect();
+        assert_eq!(
+            commits,
+            vec!["first".into(), "second".into()] as Vec<Box<str>>
+        );
+
+        // The next run overwrites the leftover temporary file.
+        append_result(&path, info("third")).unwrap();
+        let commits: Vec<_> = read().into_iter().map(|info| info.commit).collect();
+        assert_eq!(
+            commits,
+            vec!["first".into(), "second".into(), "third".into()] as Vec<Box<str>>
+        );
+        assert!(!path.with_extension("json.tmp").exists());
+
+        // The lock is released when dropped.
+        let lock = OutputLock::acquire(&path).unwrap();
+        assert!(path.with_extension("json.lock").exists());
+        drop(lock);
+        assert!(!path.with_extension("json.lock").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn markdown_summary() {
+        let mut built_ins = suite(
+            "built-ins",
+            vec![],
+            vec![
+                test("passing", TestOutcomeResult::Passed),
+                test("panicking", TestOutcomeResult::Panic),
+            ],
+        );
+        built_ins.total = 2;
+        built_ins.passed = 1;
+        built_ins.panic = 1;
+
+        let mut language = suite(
+            "language",
+            vec![],
+            vec![test("passing", TestOutcomeResult::Passed)],
+        );
+        language.total = 1;
+        language.passed = 1;
+
+        let mut results = suite("test", vec![built_ins, language], vec![]);
+        results.total = 3;
+        results.passed = 2;
+        results.panic = 1;
+
+        let markdown = render_markdown(&results);
+
+        assert!(markdown.contains("| Total | Passed | Ignored | Panics | Conformance |"));
+        assert!(markdown.contains("| 3 | 2 | 0 | 1 | 66.67% |"));
+        assert!(markdown.contains("| Suite | Total | Passed | Ignored | Conformance |"));
+
+        let language = markdown.find("| language | 1 | 1 | 0 | 100.00% |").unwrap();
+        let built_ins = markdown.find("| built-ins | 2 | 1 | 0 | 50.00% |").unwrap();
+        assert!(language < built_ins);
+    }
+
+    #[test]
+    fn html_report() {
+        let mut failing = test("failing", TestOutcomeResult::Failed);
+        failing.result_text = "expected <1>".into();
+
+        let mut built_ins = suite(
+            "built-ins",
+            vec![],
+            vec![test("passing", TestOutcomeResult::Passed), failing],
+        );
+        built_ins.total = 2;
+        built_ins.passed = 1;
+        built_ins.failed = 1;
+
+        let mut results = suite("test", vec![built_ins], vec![]);
+        results.total = 2;
+        results.passed = 1;
+        results.failed = 1;
+
+        let html = render_html(&results);
+
+        assert!(html.contains("<b>test</b>"));
+        assert!(html.contains("<b>built-ins</b>"));
+        assert!(html.contains("1 / 2 passed, 0 ignored, 1 failed, 0 panics (50.00%)"));
+        assert!(html.contains("<span>failing</span><pre>expected &lt;1&gt;</pre>"));
+        assert!(!html.contains("<span>passing</span>"));
+    }
+
+    #[test]
+    fn feature_statistics() {
+        let with_features = |name: &str, result, features: &[&str]| TestResult {
+            features: features.iter().map(|&feature| feature.into()).collect(),
+            ..test(name, result)
+        };
+
+        let sub_suite = suite(
+            "optional-chaining",
+            vec![],
+            vec![
+                with_features("a", TestOutcomeResult::Failed, &["optional-chaining"]),
+                with_features(
+                    "b",
+                    TestOutcomeResult::Passed,
+                    &["optional-chaining", "BigInt"],
+                ),
+            ],
+        );
+        let results = suite(
+            "test",
+            vec![sub_suite],
+            vec![
+                with_features("c", TestOutcomeResult::Passed, &["BigInt"]),
+                with_features("d", TestOutcomeResult::Passed, &[]),
+            ],
+        );
+
+        let stats = feature_stats(&results);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats["BigInt"],
+            FeatureResult {
+                total: 2,
+                passed: 2
+            }
+        );
+        assert_eq!(
+            stats["optional-chaining"],
+            FeatureResult {
+                total: 2,
+                passed: 1
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&stats).unwrap(),
+            r#"{"BigInt":{"c":2,"p":2},"optional-chaining":{"c":2,"p":1}}"#
+        );
+    }
+
+    #[test]
+    fn leaking_tests_are_sorted_by_path() {
+        let leaking = |path: &str, names: &[&str]| TestResult {
+            leaked_globals: names.iter().map(|&name| name.into()).collect(),
+            ..test(path, TestOutcomeResult::Passed)
+        };
+        let results = suite(
+            "test",
+            vec![suite(
+                "built-ins",
+                vec![],
+                vec![leaking("built-ins/b", &["b"]), leaking("built-ins/a", &[])],
+            )],
+            vec![leaking("a", &["x", "y"])],
+        );
+
+        let leaking: Vec<_> = leaking_tests(&results)
+            .iter()
+            .map(|test| (&*test.path, test.leaked_globals.join(", ")))
+            .collect();
+        assert_eq!(
+            leaking,
+            [
+                ("a.js", "x, y".to_owned()),
+                ("built-ins/b.js", "b".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn slowest_tests_come_first() {
+        let results = suite(
+            "test",
+            vec![suite(
+                "built-ins",
+                vec![],
+                vec![
+                    timed_test("built-ins/a.js", 5),
+                    timed_test("built-ins/b.js", 120),
+                    timed_test("built-ins/c.js", 30),
+                ],
+            )],
+            vec![
+                timed_test("d.js", 30),
+                timed_test("e.js", 1),
+                timed_test("f.js", 400),
+            ],
+        );
+
+        let slowest: Vec<_> = slowest_tests(&results, 4)
+            .iter()
+            .map(|test| (&*test.path, test.duration_ms))
+            .collect();
+        assert_eq!(
+            slowest,
+            [
+                ("f.js", 400),
+                ("built-ins/b.js", 120),
+                ("built-ins/c.js", 30),
+                ("d.js", 30),
+            ]
+        );
+    }
+
+    #[test]
+    fn comparison_json() {
+        let comparison = ResultsComparison {
+            new_failures: vec![ChangedTest {
+                path: "built-ins/broken".into(),
+                result: TestOutcomeResult::Failed,
+                result_text: "Uncaught Test262Error".into(),
+            }],
+            new_fixes: vec![ChangedTest {
+                path: "built-ins/fixed".into(),
+                result: TestOutcomeResult::Passed,
+                result_text: Box::default(),
+            }],
+        };
+
+        let json: serde_json::Value = serde_json::to_value(&comparison).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "new_failures": [{
+                    "path": "built-ins/broken",
+                    "result": "F",
+                    "result_text": "Uncaught Test262Error",
+                }],
+                "new_fixes": [{
+                    "path": "built-ins/fixed",
+                    "result": "O",
+                    "result_text": "",
+                }],
+            })
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Runs the tester against the bundled test262 fixture.
+
+use std::process::{Command, Output};
+
+/// Runs the tester in verbose mode on the fixture, returning its standard output.
+fn run_fixture(args: &[&str]) -> String {
+    let output = run_fixture_unchecked(args);
+    assert!(output.status.success());
+
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Runs the tester in verbose mode on the fixture, without checking its exit status.
+fn run_fixture_unchecked(args: &[&str]) -> Output {
+    fixture_command(args)
+        .output()
+        .expect("could not run the tester")
+}
+
+/// Creates the command running the tester in verbose mode on the fixture.
+fn fixture_command(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_boa_tester"));
+    command
+        .arg("--test262-path")
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/test262"
+        ))
+        .arg("--verbose")
+        .args(args);
+    command
+}
+
+#[test]
+fn async_tests_report_completion() {
+    let stdout = run_fixture(&[]);
+    assert!(
+        stdout.contains(
+            "Suite async: total: 3, passed: 1, ignored: 0, failed: 1, panics: 0, timed out: 1,"
+        ),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn negative_runtime_tests_check_the_error_type() {
+    let stdout = run_fixture(&[]);
+    assert!(
+        stdout.contains("Suite negative: total: 3, passed: 1,"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn negative_parse_tests_check_the_error_type() {
+    let stdout = run_fixture(&["--filter", "^parse/"]);
+    assert!(
+        stdout.contains("Suite parse: total: 2, passed: 1, ignored: 0, failed: 1, panics: 0,"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn failures_are_split_by_phase() {
+    let stdout = run_fixture(&["--filter", "^failures/"]);
+    assert!(stdout.contains("Failed tests: 2"), "{}", stdout);
+    assert!(stdout.contains("  Parse failures: 1\n"), "{}", stdout);
+    assert!(stdout.contains("  Runtime failures: 1\n"), "{}", stdout);
+}
+
+#[test]
+fn conformance_of_no_test_is_zero() {
+    let stdout = run_fixture(&["--filter", "^missing/"]);
+    assert!(stdout.contains("Total tests: 0"), "{}", stdout);
+    assert!(stdout.contains("Conformance: 0.00%"), "{}", stdout);
+}
+
+#[test]
+fn filter_only_runs_matching_tests() {
+    let stdout = run_fixture(&["--filter", "^async/(complete|failure)"]);
+    assert!(stdout.contains("Total tests: 2"), "{}", stdout);
+    assert!(stdout.contains("Passed tests: 1"), "{}", stdout);
+    assert!(!stdout.contains("Suite negative"), "{}", stdout);
+}
+
+#[test]
+fn leaked_globals_are_reported() {
+    let stdout = run_fixture(&["--filter", "^globals/", "--leaked-globals"]);
+    assert!(
+        stdout.contains("Leaked globals:\n  globals/leaked.js: leaked\n"),
+        "{}",
+        stdout
+    );
+
+    let stdout = run_fixture(&["--filter", "^globals/"]);
+    assert!(!stdout.contains("Leaked globals"), "{}", stdout);
+}
+
+#[test]
+fn strict_mode_is_applied() {
+    let stdout = run_fixture(&[]);
+    assert!(
+        stdout.contains("Suite strictness: total: 1, passed: 0,"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn fail_on_panic_sets_the_exit_code() {
+    let stdout = run_fixture(&["--filter", "^panic/"]);
+    assert!(
+        stdout.contains("Suite panic: total: 4, passed: 0,"),
+        "{}",
+        stdout
+    );
+
+    let output = run_fixture_unchecked(&["--filter", "^panic/", "--fail-on-panic"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn max_panics_aborts_the_run() {
+    let output =
+        run_fixture_unchecked(&["--filter", "^panic/", "--threads", "1", "--max-panics", "2"]);
+    assert!(!output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.contains("Total tests: 3"), "{}", stdout);
+    assert!(
+        stderr.contains("the run was aborted after more than 2 panics"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn progress_falls_back_to_dots_without_a_terminal() {
+    let stdout = run_fixture(&["--filter", "^async/", "--progress"]);
+    assert!(!stdout.contains("3/3"), "{}", stdout);
+    assert!(stdout.contains("Total tests: 3"), "{}", stdout);
+}
+
+#[test]
+fn quiet_hides_the_dots() {
+    let is_dots = |line: &str| !line.is_empty() && line.chars().all(|c| c == '.');
+
+    let stdout = run_fixture(&["--filter", "^async/"]);
+    assert!(stdout.lines().any(is_dots), "{}", stdout);
+
+    let stdout = run_fixture(&["--filter", "^async/", "--quiet"]);
+    assert!(!stdout.lines().any(is_dots), "{}", stdout);
+    assert!(
+        stdout.contains("Suite async: total: 3, passed: 1,"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Total tests: 3"), "{}", stdout);
+}
+
+#[test]
+fn rerun_failures_only_runs_failed_tests() {
+    let results = r#"{"v":1,"c":"","u":"","r":{"n":"test","c":3,"p":1,"i":0,"s":[
+        {"n":"async","c":2,"p":1,"i":0,"t":[
+            {"n":"complete","p":"async/complete.js","r":"O"},
+            {"n":"failure","p":"async/failure.js","r":"F"}
+        ]},
+        {"n":"negative","c":1,"p":0,"i":0,"t":[
+            {"n":"runtime-no-error","p":"negative/runtime-no-error.js","r":"P"}
+        ]}
+    ]}}"#;
+    let path = std::env::temp_dir().join("boa_tester_rerun_failures.json");
+    std::fs::write(&path, results).expect("could not write the previous results");
+
+    let stdout = run_fixture(&["--rerun-failures", path.to_str().unwrap()]);
+    assert!(stdout.contains("Total tests: 2"), "{}", stdout);
+    assert!(
+        stdout.contains("Suite async: total: 1, passed: 0,"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Suite negative: total: 1, passed: 0,"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Suite strictness"), "{}", stdout);
+}
+
+#[test]
+fn suites_record_their_duration() {
+    let stdout = run_fixture(&[]);
+    let duration = |suite: &str| -> u64 {
+        let line = stdout
+            .lines()
+            .find(|line| line.starts_with(&format!("Suite {}:", suite)))
+            .unwrap_or_else(|| panic!("suite {} not found: {}", suite, stdout));
+        let start = line.rfind("duration: ").unwrap() + "duration: ".len();
+        line[start..].trim_end_matches(" ms").parse().unwrap()
+    };
+
+    // Sub-suites run in parallel, so the total is only known to cover the slowest of them.
+    let total = duration("test");
+    assert!(total > 0, "{}", stdout);
+    for suite in &["async", "negative", "panic", "parse", "strictness"] {
+        assert!(total >= duration(suite), "{}", stdout);
+    }
+    assert!(stdout.contains("Elapsed time: "), "{}", stdout);
+}
+
+#[test]
+fn ndjson_output_streams_the_suite_results() {
+    let output = std::env::temp_dir().join("boa_tester_ndjson_output");
+    let _ = std::fs::remove_dir_all(&output);
+
+    run_fixture(&[
+        "--filter",
+        "^(async|negative)/",
+        "--output",
+        output.to_str().unwrap(),
+        "--output-format",
+        "ndjson",
+    ]);
+
+    let results =
+        std::fs::read_to_string(output.join("results.ndjson")).expect("no ndjson results");
+    let suites: Vec<serde_json::Value> = results
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("invalid JSON line"))
+        .collect();
+    assert!(
+        suites.iter().all(serde_json::Value::is_object),
+        "{}",
+        results
+    );
+
+    let mut names: Vec<_> = suites.iter().map(|suite| suite["s"].as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [Some("test"), Some("test/async"), Some("test/negative")],
+        "{}",
+        results
+    );
+    assert_eq!(suites.last().unwrap()["s"], "test", "{}", results);
+    assert_eq!(suites.last().unwrap()["t"], 6, "{}", results);
+    assert!(!output.join("results.json").exists());
+    assert!(output.join("latest.json").exists());
+}
+
+#[test]
+fn color_can_be_disabled() {
+    let has_escapes = |stdout: &str| stdout.contains('\u{1b}');
+
+    let stdout = run_fixture(&["--filter", "^async/", "--color", "always"]);
+    assert!(has_escapes(&stdout), "{}", stdout);
+
+    let stdout = run_fixture(&["--filter", "^async/", "--color", "never"]);
+    assert!(!has_escapes(&stdout), "{}", stdout);
+
+    // The output is not a terminal.
+    let stdout = run_fixture(&["--filter", "^async/"]);
+    assert!(!has_escapes(&stdout), "{}", stdout);
+}
+
+#[test]
+fn update_ignore_lists_the_failures() {
+    let dir = std::env::temp_dir().join("boa_tester_update_ignore");
+    std::fs::create_dir_all(&dir).unwrap();
+    let ignore_file = dir.join("test_ignore.txt");
+    std::fs::write(&ignore_file, "// Known failures.\n\nasync/removed.js\n").unwrap();
+
+    // The confirmation is required.
+    let output = fixture_command(&["--filter", "^async/", "--update-ignore"])
+        .current_dir(&dir)
+        .output()
+        .expect("could not run the tester");
+    assert!(!output.status.success());
+
+    let output = fixture_command(&["--filter", "^async/", "--update-ignore", "--confirm"])
+        .current_dir(&dir)
+        .output()
+        .expect("could not run the tester");
+    assert!(output.status.success());
+
+    // `failure.js` fails and `not-done.js` times out.
+    assert_eq!(
+        std::fs::read_to_string(&ignore_file).unwrap(),
+        "// Known failures.\n\nasync/failure.js\nasync/not-done.js\n"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_executes_a_single_test() {
+    let test = |path: &str| {
+        format!(
+            "{}/tests/fixtures/test262/test/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            path
+        )
+    };
+
+    let output = run_fixture_unchecked(&["run", &test("parse/wrong-type.js")]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success());
+    assert!(
+        stdout.contains("Test parse/wrong-type.js: fail\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("expected a ReferenceError while parsing, got a SyntaxError"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Results:"), "{}", stdout);
+
+    let stdout = run_fixture(&["run", &test("async/complete.js")]);
+    assert!(
+        stdout.contains("Test async/complete.js: pass\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn tests_stuck_in_builtins_time_out() {
+    // The test is kept out of the suite, since it only stops with a timeout.
+    let test = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/stuck/in-builtin.js"
+    );
+
+    let output = run_fixture_unchecked(&["--timeout", "1", "run", test]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success());
+    assert!(stdout.contains(": timeout\n"), "{}", stdout);
+}
@@ -0,0 +1,5 @@
+/*---
+description: Test stuck in a builtin, which doesn't check the interrupt of the context.
+---*/
+
+Array.prototype.indexOf.call({ length: 4294967295 }, 1);
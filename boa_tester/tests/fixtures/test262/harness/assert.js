@@ -0,0 +1,7 @@
+function assert(mustBeTrue, message) {
+  if (mustBeTrue === true) { return; }
+  throw new Test262Error(message);
+}
+assert.sameValue = function (actual, expected, message) {
+  if (actual !== expected) { throw new Test262Error(message); }
+};
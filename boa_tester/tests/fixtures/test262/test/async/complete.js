@@ -0,0 +1,7 @@
+/*---
+description: Asynchronous test reporting its completion.
+flags: [async]
+---*/
+
+assert.sameValue(1 + 1, 2);
+$DONE();
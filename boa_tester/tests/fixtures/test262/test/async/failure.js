@@ -0,0 +1,6 @@
+/*---
+description: Asynchronous test reporting a failure.
+flags: [async]
+---*/
+
+$DONE(new Test262Error("expected failure"));
@@ -0,0 +1,6 @@
+/*---
+description: Asynchronous test that never calls $DONE.
+flags: [async]
+---*/
+
+assert.sameValue(1 + 1, 2);
@@ -0,0 +1,5 @@
+/*---
+description: A positive test that is parsed but throws is a runtime failure.
+---*/
+
+throw new Test262Error("expected failure");
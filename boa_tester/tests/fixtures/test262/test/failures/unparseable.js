@@ -0,0 +1,5 @@
+/*---
+description: A positive test that can't be parsed is a parse failure.
+---*/
+
+var = 1;
@@ -0,0 +1,6 @@
+/*---
+description: Sets a property on the global object, which the test leaves behind.
+---*/
+
+var declared = 1;
+globalThis.leaked = 1;
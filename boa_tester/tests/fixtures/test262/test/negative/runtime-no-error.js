@@ -0,0 +1,8 @@
+/*---
+description: Not throwing at all fails the test.
+negative:
+  phase: runtime
+  type: TypeError
+---*/
+
+var x = 1;
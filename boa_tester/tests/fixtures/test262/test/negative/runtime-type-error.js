@@ -0,0 +1,8 @@
+/*---
+description: Calling `undefined` throws a TypeError at runtime.
+negative:
+  phase: runtime
+  type: TypeError
+---*/
+
+undefined();
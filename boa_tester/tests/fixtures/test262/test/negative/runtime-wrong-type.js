@@ -0,0 +1,8 @@
+/*---
+description: Throwing a different error than the expected one fails the test.
+negative:
+  phase: runtime
+  type: ReferenceError
+---*/
+
+undefined();
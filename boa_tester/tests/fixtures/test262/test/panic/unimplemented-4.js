@@ -0,0 +1,5 @@
+/*---
+description: Another panic, so that there are several of them.
+---*/
+
+(1).toPrecision(2);
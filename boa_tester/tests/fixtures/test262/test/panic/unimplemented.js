@@ -0,0 +1,5 @@
+/*---
+description: Calling an unimplemented built-in makes the engine panic.
+---*/
+
+(1).toPrecision(2);
@@ -0,0 +1,8 @@
+/*---
+description: A syntax error is reported while parsing.
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+var = 1;
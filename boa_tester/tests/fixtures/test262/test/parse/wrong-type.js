@@ -0,0 +1,8 @@
+/*---
+description: Expecting a parse error other than a SyntaxError fails the test.
+negative:
+  phase: parse
+  type: ReferenceError
+---*/
+
+var = 1;
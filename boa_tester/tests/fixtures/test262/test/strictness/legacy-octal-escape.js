@@ -0,0 +1,5 @@
+/*---
+description: Legacy octal escapes are only allowed in non-strict code, so the two modes disagree.
+---*/
+
+assert.sameValue("\101", "A");